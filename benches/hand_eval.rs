@@ -0,0 +1,66 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use poker_game::card::{Card, Rank, Suit};
+use poker_game::hand_eval::{evaluate_best_hand, HandRank, HandValue};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+fn sample_hands(count: usize, size: usize) -> Vec<Vec<Card>> {
+    let mut deck: Vec<Card> = Suit::ALL
+        .iter()
+        .flat_map(|&suit| Rank::ALL.iter().map(move |&rank| Card::new(rank, suit)))
+        .collect();
+    let mut rng = StdRng::seed_from_u64(52);
+
+    (0..count)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            deck[..size].to_vec()
+        })
+        .collect()
+}
+
+fn bench_evaluators(c: &mut Criterion) {
+    let fives = sample_hands(1000, 5);
+    let sixes = sample_hands(1000, 6);
+    let sevens = sample_hands(1000, 7);
+
+    c.bench_function("hand_rank_5", |b| {
+        b.iter(|| {
+            for hand in &fives {
+                black_box(HandRank::evaluate(hand[..].try_into().unwrap()));
+            }
+        })
+    });
+    c.bench_function("hand_value_5", |b| {
+        b.iter(|| {
+            for hand in &fives {
+                black_box(HandValue::evaluate(hand).unwrap());
+            }
+        })
+    });
+    c.bench_function("hand_value_6", |b| {
+        b.iter(|| {
+            for hand in &sixes {
+                black_box(HandValue::evaluate(hand).unwrap());
+            }
+        })
+    });
+    c.bench_function("evaluate_best_hand_7", |b| {
+        b.iter(|| {
+            for hand in &sevens {
+                black_box(evaluate_best_hand(&hand[..2], &hand[2..]));
+            }
+        })
+    });
+    c.bench_function("hand_value_7", |b| {
+        b.iter(|| {
+            for hand in &sevens {
+                black_box(HandValue::evaluate(hand).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_evaluators);
+criterion_main!(benches);
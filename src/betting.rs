@@ -1,3 +1,10 @@
+use crate::card::Card;
+use crate::player::ChipError;
+use crate::table::{Positions, Table};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Action {
     Fold,
     Check,
@@ -5,30 +12,1297 @@ pub enum Action {
     Raise(u32),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BettingError {
+    CannotCheck,
+    NothingToCall,
+    WrongCallAmount { expected: u32 },
+    InvalidRaise,
+    InsufficientChips,
+    RaiseTooSmall { min: u32 },
+    RaiseTooLarge { max: u32 },
+    RaiseCapReached,
+    ActionNotReopened,
+}
+
+impl fmt::Display for BettingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BettingError::CannotCheck => write!(f, "Cannot check when facing a bet"),
+            BettingError::NothingToCall => write!(f, "There is no bet to call"),
+            BettingError::WrongCallAmount { expected } => {
+                write!(f, "A call must be for exactly {} chips", expected)
+            }
+            BettingError::InvalidRaise => write!(f, "A raise must be for more than zero chips"),
+            BettingError::InsufficientChips => write!(f, "Not enough chips for that action"),
+            BettingError::RaiseTooSmall { min } => {
+                write!(f, "A raise must be at least {} chips", min)
+            }
+            BettingError::RaiseTooLarge { max } => {
+                write!(f, "A raise can be at most {} chips", max)
+            }
+            BettingError::RaiseCapReached => write!(f, "The betting is capped on this street"),
+            BettingError::ActionNotReopened => {
+                write!(f, "An incomplete all-in raise does not reopen the betting")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BettingContext {
+    pub to_call: u32,
+    pub chips: u32,
+    pub pot: u32,
+    pub min_raise: u32,
+    pub raises: u32,
+    pub big_bet_street: bool,
+    pub can_raise: bool,
+    pub bring_in: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BettingStructure {
+    NoLimit,
+    PotLimit,
+    FixedLimit {
+        small_bet: u32,
+        big_bet: u32,
+        max_raises: u32,
+    },
+}
+
+impl BettingStructure {
+    pub fn opening_raise(&self, big_blind: u32, big_bet_street: bool) -> u32 {
+        match *self {
+            BettingStructure::NoLimit | BettingStructure::PotLimit => big_blind,
+            BettingStructure::FixedLimit {
+                small_bet, big_bet, ..
+            } => {
+                if big_bet_street {
+                    big_bet
+                } else {
+                    small_bet
+                }
+            }
+        }
+    }
+
+    pub fn min_raise(&self, context: &BettingContext) -> u32 {
+        match *self {
+            BettingStructure::NoLimit | BettingStructure::PotLimit => context.min_raise,
+            BettingStructure::FixedLimit {
+                small_bet, big_bet, ..
+            } => {
+                let bet = if context.big_bet_street {
+                    big_bet
+                } else {
+                    small_bet
+                };
+                // Completing a bring-in only takes the rest of the small bet.
+                bet.saturating_sub(context.bring_in)
+            }
+        }
+    }
+
+    pub fn raise_limits(&self, context: &BettingContext) -> Option<(u32, u32)> {
+        let all_in = context.chips.saturating_sub(context.to_call);
+        if !context.can_raise || all_in == 0 {
+            return None;
+        }
+
+        let min = self.min_raise(context);
+        let max = match *self {
+            BettingStructure::NoLimit => all_in,
+            BettingStructure::PotLimit => (context.pot + context.to_call).min(all_in),
+            BettingStructure::FixedLimit { max_raises, .. } => {
+                if context.raises >= max_raises {
+                    return None;
+                }
+                min
+            }
+        };
+
+        let min = min.min(all_in);
+        Some((min, max.min(all_in).max(min)))
+    }
+
+    pub fn normalize(
+        &self,
+        action: Action,
+        context: &BettingContext,
+    ) -> Result<Action, BettingError> {
+        match action {
+            Action::Fold => Ok(Action::Fold),
+            Action::Check if context.to_call > 0 => Err(BettingError::CannotCheck),
+            Action::Check => Ok(Action::Check),
+            Action::Call(_) if context.to_call == 0 => Err(BettingError::NothingToCall),
+            Action::Call(amount) => {
+                let expected = context.to_call.min(context.chips);
+                if amount != expected {
+                    return Err(BettingError::WrongCallAmount { expected });
+                }
+                Ok(Action::Call(amount))
+            }
+            Action::Raise(0) => Err(BettingError::InvalidRaise),
+            Action::Raise(amount) => {
+                // A fixed-limit raise is always the fixed size, whatever was
+                // asked, or an incomplete all-in when the player is short.
+                let amount = match self {
+                    BettingStructure::FixedLimit { .. } => self
+                        .min_raise(context)
+                        .min(context.chips.saturating_sub(context.to_call)),
+                    _ => amount,
+                };
+                if amount == 0 || context.to_call + amount > context.chips {
+                    return Err(BettingError::InsufficientChips);
+                }
+                if !context.can_raise {
+                    return Err(BettingError::ActionNotReopened);
+                }
+                let (min, max) = self
+                    .raise_limits(context)
+                    .ok_or(BettingError::RaiseCapReached)?;
+
+                if amount < min {
+                    return Err(BettingError::RaiseTooSmall { min });
+                }
+                if amount > max {
+                    return Err(BettingError::RaiseTooLarge { max });
+                }
+                Ok(Action::Raise(amount))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForcedBetKind {
+    Ante,
+    SmallBlind,
+    BigBlind,
+    Straddle,
+    BringIn,
+}
+
+impl ForcedBetKind {
+    pub fn is_live(&self) -> bool {
+        *self != ForcedBetKind::Ante
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForcedBet {
+    pub seat: usize,
+    pub kind: ForcedBetKind,
+    pub amount: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlindStructure {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+    pub big_blind_ante: bool,
+    pub straddle: bool,
+}
+
+impl Default for BlindStructure {
+    fn default() -> Self {
+        Self::new(25, 50)
+    }
+}
+
+impl BlindStructure {
+    pub fn new(small_blind: u32, big_blind: u32) -> Self {
+        Self {
+            small_blind,
+            big_blind,
+            ante: 0,
+            big_blind_ante: false,
+            straddle: false,
+        }
+    }
+
+    pub fn straddle_amount(&self) -> u32 {
+        self.big_blind * 2
+    }
+
+    pub fn post(
+        &self,
+        table: &mut Table,
+        positions: &Positions,
+    ) -> Result<Vec<ForcedBet>, ChipError> {
+        let mut bets = Vec::new();
+
+        if !self.big_blind_ante {
+            self.post_antes(table, &mut bets)?;
+        }
+
+        if let Some(seat) = positions.small_blind {
+            Self::post_one(
+                table,
+                &mut bets,
+                seat,
+                ForcedBetKind::SmallBlind,
+                self.small_blind,
+            )?;
+        }
+        Self::post_one(
+            table,
+            &mut bets,
+            positions.big_blind,
+            ForcedBetKind::BigBlind,
+            self.big_blind,
+        )?;
+        if self.big_blind_ante {
+            Self::post_one(
+                table,
+                &mut bets,
+                positions.big_blind,
+                ForcedBetKind::Ante,
+                self.ante,
+            )?;
+        }
+
+        if self.straddle && table.active_count() > 2 {
+            if let Some(seat) = table
+                .next_active(positions.big_blind)
+                .filter(|&seat| seat != positions.button)
+            {
+                Self::post_one(
+                    table,
+                    &mut bets,
+                    seat,
+                    ForcedBetKind::Straddle,
+                    self.straddle_amount(),
+                )?;
+            }
+        }
+
+        Ok(bets)
+    }
+
+    // Stud has no blinds: everyone antes and the small blind is the bring-in.
+    pub fn post_stud(
+        &self,
+        table: &mut Table,
+        bring_in: usize,
+    ) -> Result<Vec<ForcedBet>, ChipError> {
+        let mut bets = Vec::new();
+        self.post_antes(table, &mut bets)?;
+        Self::post_one(
+            table,
+            &mut bets,
+            bring_in,
+            ForcedBetKind::BringIn,
+            self.small_blind,
+        )?;
+        Ok(bets)
+    }
+
+    fn post_antes(&self, table: &mut Table, bets: &mut Vec<ForcedBet>) -> Result<(), ChipError> {
+        if self.ante == 0 {
+            return Ok(());
+        }
+
+        let seats: Vec<usize> = table
+            .players()
+            .filter(|(_, player)| !player.is_busted())
+            .map(|(seat, _)| seat)
+            .collect();
+        for seat in seats {
+            Self::post_one(table, bets, seat, ForcedBetKind::Ante, self.ante)?;
+        }
+        Ok(())
+    }
+
+    fn post_one(
+        table: &mut Table,
+        bets: &mut Vec<ForcedBet>,
+        seat: usize,
+        kind: ForcedBetKind,
+        amount: u32,
+    ) -> Result<(), ChipError> {
+        let Some(player) = table.player_mut(seat) else {
+            return Ok(());
+        };
+        if amount == 0 || player.is_busted() {
+            return Ok(());
+        }
+
+        let amount = player.call(amount)?;
+        bets.push(ForcedBet { seat, kind, amount });
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SeatStatus {
+    Out,
+    Active,
+    AllIn,
+    Folded,
+}
+
 pub struct Round {
-    is_over: bool,
+    status: Vec<SeatStatus>,
+    committed: Vec<u32>,
+    has_acted: Vec<bool>,
+    current_bet: u32,
+    last_raise: u32,
+    raises: u32,
+    short_raises: u32,
+    completion: Option<u32>,
+    to_act: Option<usize>,
 }
 
 impl Round {
-    pub fn new() -> Self {
-        Self { is_over: false }
+    pub fn new(seat_count: usize, min_raise: u32) -> Self {
+        Self {
+            status: vec![SeatStatus::Out; seat_count],
+            committed: vec![0; seat_count],
+            has_acted: vec![false; seat_count],
+            current_bet: 0,
+            last_raise: min_raise,
+            raises: 0,
+            short_raises: 0,
+            completion: None,
+            to_act: None,
+        }
     }
 
-    pub fn end_round(&mut self) {
-        self.is_over = true;
+    pub fn join(&mut self, seat: usize, all_in: bool) {
+        self.status[seat] = if all_in {
+            SeatStatus::AllIn
+        } else {
+            SeatStatus::Active
+        };
+    }
+
+    pub fn post(&mut self, seat: usize, amount: u32, all_in: bool) {
+        self.committed[seat] += amount;
+        self.current_bet = self.current_bet.max(self.committed[seat]);
+        if all_in {
+            self.status[seat] = SeatStatus::AllIn;
+        }
+    }
+
+    // A bring-in may be completed to a full bet. The completion is the bet
+    // itself, so it does not count toward the raise cap.
+    pub fn open_with_bring_in(&mut self, full_bet: u32) {
+        self.completion = Some(full_bet);
+        self.last_raise = full_bet.saturating_sub(self.current_bet);
+    }
+
+    pub fn start(&mut self, after: usize) {
+        self.to_act = Some(after);
+        self.pass_turn();
+    }
+
+    pub fn act(&mut self, action: Action, all_in: bool) {
+        let Some(seat) = self.to_act else {
+            return;
+        };
+
+        match action {
+            Action::Fold => self.status[seat] = SeatStatus::Folded,
+            Action::Check => {}
+            Action::Call(amount) => self.committed[seat] += amount,
+            Action::Raise(amount) => {
+                let had_bet = self.current_bet > 0 && self.completion.is_none();
+                self.committed[seat] += self.to_call(seat) + amount;
+                self.current_bet = self.committed[seat];
+
+                // Short all-in raises add up, and reopen the action once
+                // together they make a full raise.
+                self.short_raises += amount;
+                if amount >= self.last_raise || self.short_raises >= self.last_raise {
+                    if had_bet {
+                        self.raises += 1;
+                    }
+                    if amount >= self.last_raise {
+                        self.last_raise = self
+                            .completion
+                            .take()
+                            .map_or(amount, |full_bet| amount.max(full_bet));
+                    }
+                    self.short_raises = 0;
+                    self.has_acted.iter_mut().for_each(|acted| *acted = false);
+                }
+            }
+        }
+
+        if all_in && self.status[seat] == SeatStatus::Active {
+            self.status[seat] = SeatStatus::AllIn;
+        }
+        self.has_acted[seat] = true;
+        self.pass_turn();
+    }
+
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.to_act.is_none()
+    }
+
+    pub fn current_bet(&self) -> u32 {
+        self.current_bet
+    }
+
+    pub fn committed(&self, seat: usize) -> u32 {
+        self.committed.get(seat).copied().unwrap_or(0)
+    }
+
+    pub fn to_call(&self, seat: usize) -> u32 {
+        self.current_bet - self.committed(seat)
+    }
+
+    pub fn last_raise(&self) -> u32 {
+        self.last_raise
+    }
+
+    pub fn raises(&self) -> u32 {
+        self.raises
+    }
+
+    pub fn bring_in(&self) -> u32 {
+        self.completion.map_or(0, |_| self.current_bet)
+    }
+
+    pub fn can_raise(&self, seat: usize) -> bool {
+        !self.has_acted[seat]
+    }
+
+    pub fn players_left(&self) -> usize {
+        self.status
+            .iter()
+            .filter(|&&status| matches!(status, SeatStatus::Active | SeatStatus::AllIn))
+            .count()
+    }
+
+    fn is_closed(&self) -> bool {
+        if self.players_left() <= 1 {
+            return true;
+        }
+
+        let actors: Vec<usize> = (0..self.status.len())
+            .filter(|&seat| self.status[seat] == SeatStatus::Active)
+            .collect();
+
+        if actors.len() <= 1 {
+            return actors
+                .iter()
+                .all(|&seat| self.has_acted[seat] || self.committed[seat] >= self.current_bet);
+        }
+
+        actors
+            .iter()
+            .all(|&seat| self.has_acted[seat] && self.committed[seat] == self.current_bet)
+    }
+
+    fn pass_turn(&mut self) {
+        let Some(from) = self.to_act else {
+            return;
+        };
+
+        if self.is_closed() {
+            self.to_act = None;
+            return;
+        }
+
+        let seats = self.status.len();
+        self.to_act = (1..=seats)
+            .map(|offset| (from + offset) % seats)
+            .find(|&seat| self.status[seat] == SeatStatus::Active);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SidePot {
+    pub amount: u32,
+    pub eligible: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OddChipRule {
+    LeftOfButton,
+    HighestCard,
+}
+
+impl OddChipRule {
+    pub fn seat_order(
+        &self,
+        button: usize,
+        seat_count: usize,
+        high_card: impl Fn(usize) -> Option<Card>,
+    ) -> Vec<usize> {
+        let mut seats: Vec<usize> = (1..=seat_count)
+            .map(|offset| (button + offset) % seat_count)
+            .collect();
+
+        if *self == OddChipRule::HighestCard {
+            seats.sort_by_key(|&seat| {
+                std::cmp::Reverse(
+                    high_card(seat).map(|card| (card.get_rank(), card.get_suit().tie_break_rank())),
+                )
+            });
+        }
+
+        seats
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PotAward {
+    pub amount: u32,
+    pub winners: Vec<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PayoutReport {
+    pub awards: Vec<PotAward>,
+    payouts: BTreeMap<usize, u32>,
+}
+
+impl PayoutReport {
+    pub fn amount(&self, seat: usize) -> u32 {
+        self.payouts.get(&seat).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.payouts.values().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.payouts.iter().map(|(&seat, &amount)| (seat, amount))
+    }
+
+    pub fn winners(&self) -> Vec<usize> {
+        self.payouts.keys().copied().collect()
+    }
+
+    fn award(&mut self, amount: u32, winners: Vec<usize>, odd_chip_order: &[usize]) {
+        if winners.is_empty() {
+            return;
+        }
+
+        let share = amount / winners.len() as u32;
+        let mut odd_chips = amount % winners.len() as u32;
+        for &seat in &winners {
+            *self.payouts.entry(seat).or_insert(0) += share;
+        }
+        for seat in odd_chip_order.iter().filter(|seat| winners.contains(seat)) {
+            if odd_chips == 0 {
+                break;
+            }
+            *self.payouts.entry(*seat).or_insert(0) += 1;
+            odd_chips -= 1;
+        }
+
+        self.awards.push(PotAward { amount, winners });
+    }
+
+    pub fn apply(&self, table: &mut Table) -> Result<(), ChipError> {
+        for (seat, amount) in self.iter() {
+            if let Some(player) = table.player_mut(seat) {
+                player.collect(amount)?;
+            }
+        }
+        Ok(())
     }
 }
 
 pub struct Pot {
-    total: u32,
+    contributions: BTreeMap<usize, u32>,
+    folded: BTreeSet<usize>,
+}
+
+impl Default for Pot {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Pot {
     pub fn new() -> Self {
-        Self { total: 0 }
+        Self {
+            contributions: BTreeMap::new(),
+            folded: BTreeSet::new(),
+        }
+    }
+
+    pub fn add(&mut self, seat: usize, amount: u32) -> Result<(), ChipError> {
+        self.total()
+            .checked_add(amount)
+            .ok_or(ChipError::Overflow)?;
+        *self.contributions.entry(seat).or_insert(0) += amount;
+        Ok(())
+    }
+
+    pub fn fold(&mut self, seat: usize) {
+        self.folded.insert(seat);
+    }
+
+    pub fn total(&self) -> u32 {
+        self.contributions.values().sum()
+    }
+
+    pub fn contribution(&self, seat: usize) -> u32 {
+        self.contributions.get(&seat).copied().unwrap_or(0)
+    }
+
+    pub fn clear(&mut self) {
+        self.contributions.clear();
+        self.folded.clear();
+    }
+
+    pub fn side_pots(&self) -> Vec<SidePot> {
+        let mut levels: Vec<u32> = self
+            .contributions
+            .iter()
+            .filter(|(seat, &amount)| amount > 0 && !self.folded.contains(seat))
+            .map(|(_, &amount)| amount)
+            .collect();
+        levels.sort();
+        levels.dedup();
+
+        let mut pots: Vec<SidePot> = Vec::with_capacity(levels.len());
+        let mut previous = 0;
+        for level in levels {
+            let amount = self
+                .contributions
+                .values()
+                .map(|&contributed| contributed.min(level) - contributed.min(previous))
+                .sum();
+            let eligible = self
+                .contributions
+                .iter()
+                .filter(|(seat, &contributed)| contributed >= level && !self.folded.contains(seat))
+                .map(|(&seat, _)| seat)
+                .collect();
+            pots.push(SidePot { amount, eligible });
+            previous = level;
+        }
+
+        let leftover: u32 = self
+            .contributions
+            .values()
+            .map(|&contributed| contributed.saturating_sub(previous))
+            .sum();
+        match pots.last_mut() {
+            Some(last) => last.amount += leftover,
+            // Everyone who put chips in has folded, so whoever is left in
+            // the hand takes them.
+            None if leftover > 0 => pots.push(SidePot {
+                amount: leftover,
+                eligible: self
+                    .contributions
+                    .keys()
+                    .copied()
+                    .filter(|seat| !self.folded.contains(seat))
+                    .collect(),
+            }),
+            None => {}
+        }
+
+        pots
+    }
+
+    pub fn pay_out<R: Ord>(
+        &self,
+        strength: impl Fn(usize) -> R,
+        odd_chip_order: &[usize],
+    ) -> PayoutReport {
+        let mut report = PayoutReport::default();
+
+        for side_pot in self.side_pots() {
+            let winners = best_seats(&side_pot.eligible, &strength);
+            report.award(side_pot.amount, winners, odd_chip_order);
+        }
+
+        report
+    }
+
+    // High/low games split every pot between the best high and the best
+    // qualifying low; the high half keeps the odd chip, and with no
+    // qualifying low the high hand scoops.
+    pub fn pay_out_split<H: Ord, L: Ord>(
+        &self,
+        high: impl Fn(usize) -> H,
+        low: impl Fn(usize) -> Option<L>,
+        odd_chip_order: &[usize],
+    ) -> PayoutReport {
+        let mut report = PayoutReport::default();
+
+        for side_pot in self.side_pots() {
+            let high_winners = best_seats(&side_pot.eligible, &high);
+            let has_low = side_pot.eligible.iter().any(|&seat| low(seat).is_some());
+            if !has_low {
+                report.award(side_pot.amount, high_winners, odd_chip_order);
+                continue;
+            }
+
+            let low_half = side_pot.amount / 2;
+            report.award(side_pot.amount - low_half, high_winners, odd_chip_order);
+            report.award(
+                low_half,
+                best_seats(&side_pot.eligible, &low),
+                odd_chip_order,
+            );
+        }
+
+        report
+    }
+}
+
+fn best_seats<R: Ord>(eligible: &[usize], strength: impl Fn(usize) -> R) -> Vec<usize> {
+    let best = eligible.iter().map(|&seat| strength(seat)).max();
+    eligible
+        .iter()
+        .copied()
+        .filter(|&seat| Some(strength(seat)) == best)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    fn context(to_call: u32, chips: u32, pot: u32) -> BettingContext {
+        BettingContext {
+            to_call,
+            chips,
+            pot,
+            min_raise: 50,
+            raises: 0,
+            big_bet_street: false,
+            can_raise: true,
+            bring_in: 0,
+        }
+    }
+
+    #[test]
+    fn test_no_limit_raise_bounds() {
+        let structure = BettingStructure::NoLimit;
+        let context = context(50, 1000, 75);
+
+        assert_eq!(structure.raise_limits(&context), Some((50, 950)));
+        assert_eq!(
+            structure.normalize(Action::Raise(49), &context),
+            Err(BettingError::RaiseTooSmall { min: 50 })
+        );
+        assert_eq!(
+            structure.normalize(Action::Raise(950), &context),
+            Ok(Action::Raise(950))
+        );
+        assert_eq!(
+            structure.normalize(Action::Raise(951), &context),
+            Err(BettingError::InsufficientChips)
+        );
+    }
+
+    #[test]
+    fn test_short_stack_may_raise_all_in_below_the_minimum() {
+        let structure = BettingStructure::NoLimit;
+        let context = context(50, 80, 75);
+
+        assert_eq!(structure.raise_limits(&context), Some((30, 30)));
+        assert_eq!(
+            structure.normalize(Action::Raise(30), &context),
+            Ok(Action::Raise(30))
+        );
+    }
+
+    #[test]
+    fn test_pot_limit_maximum() {
+        let structure = BettingStructure::PotLimit;
+        let context = context(50, 1000, 75);
+
+        assert_eq!(structure.raise_limits(&context), Some((50, 125)));
+        assert_eq!(
+            structure.normalize(Action::Raise(126), &context),
+            Err(BettingError::RaiseTooLarge { max: 125 })
+        );
+    }
+
+    #[test]
+    fn test_fixed_limit_short_stack_raises_all_in() {
+        let structure = BettingStructure::FixedLimit {
+            small_bet: 50,
+            big_bet: 100,
+            max_raises: 3,
+        };
+
+        assert_eq!(
+            structure.normalize(Action::Raise(1), &context(50, 80, 75)),
+            Ok(Action::Raise(30))
+        );
+        assert_eq!(
+            structure.normalize(Action::Raise(1), &context(50, 50, 75)),
+            Err(BettingError::InsufficientChips)
+        );
+        assert_eq!(
+            structure.normalize(Action::Raise(5000), &context(50, 100, 75)),
+            Ok(Action::Raise(50))
+        );
+    }
+
+    #[test]
+    fn test_fixed_limit_sizes_and_cap() {
+        let structure = BettingStructure::FixedLimit {
+            small_bet: 50,
+            big_bet: 100,
+            max_raises: 3,
+        };
+        let mut context = context(0, 1000, 100);
+
+        assert_eq!(
+            structure.normalize(Action::Raise(500), &context),
+            Ok(Action::Raise(50))
+        );
+        context.big_bet_street = true;
+        assert_eq!(structure.raise_limits(&context), Some((100, 100)));
+        context.chips = 80;
+        assert_eq!(structure.raise_limits(&context), Some((80, 80)));
+        context.chips = 1000;
+        context.raises = 3;
+        assert_eq!(structure.raise_limits(&context), None);
+        assert_eq!(
+            structure.normalize(Action::Raise(100), &context),
+            Err(BettingError::RaiseCapReached)
+        );
+    }
+
+    #[test]
+    fn test_action_not_reopened() {
+        let mut context = context(20, 1000, 300);
+        context.can_raise = false;
+
+        assert_eq!(
+            BettingStructure::NoLimit.normalize(Action::Raise(100), &context),
+            Err(BettingError::ActionNotReopened)
+        );
+        assert_eq!(
+            BettingStructure::NoLimit.normalize(Action::Call(20), &context),
+            Ok(Action::Call(20))
+        );
+    }
+
+    fn preflop_round(seats: usize) -> Round {
+        let mut round = Round::new(seats, 50);
+        for seat in 0..seats {
+            round.join(seat, false);
+        }
+        round.post(1, 25, false);
+        round.post(2 % seats, 50, false);
+        round.start(2 % seats);
+        round
+    }
+
+    #[test]
+    fn test_round_closes_when_everyone_matches() {
+        let mut round = preflop_round(4);
+
+        assert_eq!(round.to_act(), Some(3));
+        round.act(Action::Call(50), false);
+        round.act(Action::Raise(100), false);
+        round.act(Action::Fold, false);
+        round.act(Action::Call(100), false);
+        assert_eq!(round.to_act(), Some(3));
+        round.act(Action::Call(100), false);
+
+        assert!(round.is_over());
+        assert_eq!(round.current_bet(), 150);
+        assert_eq!(round.committed(1), 25);
+        assert_eq!(round.players_left(), 3);
+    }
+
+    #[test]
+    fn test_big_blind_option() {
+        let mut round = preflop_round(3);
+
+        round.act(Action::Call(50), false);
+        round.act(Action::Call(25), false);
+        assert_eq!(round.to_act(), Some(2));
+        assert!(round.can_raise(2));
+        assert_eq!(round.to_call(2), 0);
+
+        round.act(Action::Check, false);
+        assert!(round.is_over());
+    }
+
+    #[test]
+    fn test_round_ends_when_one_player_remains() {
+        let mut round = preflop_round(3);
+
+        round.act(Action::Fold, false);
+        round.act(Action::Fold, false);
+        assert!(round.is_over());
+        assert_eq!(round.players_left(), 1);
+    }
+
+    #[test]
+    fn test_round_ends_when_everyone_else_is_all_in() {
+        let mut round = preflop_round(3);
+
+        round.act(Action::Raise(950), true);
+        round.act(Action::Fold, false);
+        assert_eq!(round.to_act(), Some(2));
+        round.act(Action::Call(950), false);
+        assert!(round.is_over());
+    }
+
+    #[test]
+    fn test_short_all_in_does_not_reopen_raising() {
+        let mut round = preflop_round(3);
+
+        round.act(Action::Raise(100), false);
+        round.act(Action::Call(125), false);
+        round.act(Action::Raise(30), true);
+
+        assert_eq!(round.current_bet(), 180);
+        assert_eq!(round.last_raise(), 100);
+        assert_eq!(round.to_act(), Some(0));
+        assert!(!round.can_raise(0));
+        round.act(Action::Call(30), false);
+        assert_eq!(round.to_act(), Some(1));
+        round.act(Action::Call(30), false);
+        assert!(round.is_over());
+    }
+
+    fn blinds_table(stacks: &[u32]) -> (Table<'static>, Positions) {
+        let names = ["Dusan", "Ana", "Marko", "Jelena", "Nikola"];
+        let players = stacks
+            .iter()
+            .zip(names)
+            .map(|(&chips, name)| Player::new(name, chips))
+            .collect();
+        let mut table = Table::with_players(players).unwrap();
+        table.set_button(0).unwrap();
+        let positions = table.advance_button().unwrap();
+        (table, positions)
+    }
+
+    #[test]
+    fn test_posts_blinds_and_antes() {
+        let (mut table, positions) = blinds_table(&[1000, 1000, 1000]);
+        let blinds = BlindStructure {
+            ante: 10,
+            ..BlindStructure::new(50, 100)
+        };
+
+        let bets = blinds.post(&mut table, &positions).unwrap();
+        let antes = bets.iter().filter(|bet| bet.kind == ForcedBetKind::Ante);
+        assert_eq!(antes.count(), 3);
+        assert_eq!(
+            bets[3..],
+            [
+                ForcedBet {
+                    seat: 1,
+                    kind: ForcedBetKind::SmallBlind,
+                    amount: 50
+                },
+                ForcedBet {
+                    seat: 2,
+                    kind: ForcedBetKind::BigBlind,
+                    amount: 100
+                },
+            ]
+        );
+        assert_eq!(table.total_chips(), 2820);
+    }
+
+    #[test]
+    fn test_big_blind_ante() {
+        let (mut table, positions) = blinds_table(&[1000, 1000, 1000]);
+        let blinds = BlindStructure {
+            ante: 100,
+            big_blind_ante: true,
+            ..BlindStructure::new(50, 100)
+        };
+
+        let bets = blinds.post(&mut table, &positions).unwrap();
+        assert_eq!(bets.len(), 3);
+        assert_eq!(bets[2].seat, 2);
+        assert_eq!(bets[2].kind, ForcedBetKind::Ante);
+        assert_eq!(table.player(2).unwrap().chips, 800);
+        assert_eq!(table.player(0).unwrap().chips, 1000);
+    }
+
+    #[test]
+    fn test_short_stacks_post_what_they_have() {
+        let (mut table, positions) = blinds_table(&[1000, 30, 120]);
+        let blinds = BlindStructure {
+            ante: 25,
+            ..BlindStructure::new(50, 100)
+        };
+
+        let bets = blinds.post(&mut table, &positions).unwrap();
+        assert_eq!(
+            bets.iter()
+                .map(|bet| (bet.seat, bet.amount))
+                .collect::<Vec<_>>(),
+            vec![(0, 25), (1, 25), (2, 25), (1, 5), (2, 95)]
+        );
+        assert!(table.player(1).unwrap().is_busted());
+        assert!(table.player(2).unwrap().is_busted());
+    }
+
+    #[test]
+    fn test_straddle_left_of_big_blind() {
+        let (mut table, positions) = blinds_table(&[1000, 1000, 1000, 1000]);
+        let blinds = BlindStructure {
+            straddle: true,
+            ..BlindStructure::default()
+        };
+
+        let bets = blinds.post(&mut table, &positions).unwrap();
+        assert_eq!(
+            bets.last(),
+            Some(&ForcedBet {
+                seat: 3,
+                kind: ForcedBetKind::Straddle,
+                amount: 100
+            })
+        );
+
+        let (mut table, positions) = blinds_table(&[1000, 1000, 1000]);
+        let bets = blinds.post(&mut table, &positions).unwrap();
+        assert!(bets.iter().all(|bet| bet.kind != ForcedBetKind::Straddle));
+    }
+
+    #[test]
+    fn test_stud_antes_and_bring_in() {
+        let (mut table, _) = blinds_table(&[1000, 1000, 1000]);
+        let blinds = BlindStructure {
+            ante: 10,
+            ..BlindStructure::new(25, 50)
+        };
+
+        let bets = blinds.post_stud(&mut table, 2).unwrap();
+        assert_eq!(bets.len(), 4);
+        assert_eq!(
+            bets[3],
+            ForcedBet {
+                seat: 2,
+                kind: ForcedBetKind::BringIn,
+                amount: 25
+            }
+        );
+        assert!(bets[3].kind.is_live());
+        assert_eq!(table.total_chips(), 2945);
+    }
+
+    #[test]
+    fn test_single_pot_without_all_ins() {
+        let mut pot = Pot::new();
+        pot.add(0, 100).unwrap();
+        pot.add(1, 100).unwrap();
+        pot.add(2, 100).unwrap();
+
+        assert_eq!(pot.total(), 300);
+        assert_eq!(
+            pot.side_pots(),
+            vec![SidePot {
+                amount: 300,
+                eligible: vec![0, 1, 2]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_short_all_in_creates_side_pot() {
+        let mut pot = Pot::new();
+        pot.add(0, 50).unwrap();
+        pot.add(1, 200).unwrap();
+        pot.add(2, 200).unwrap();
+
+        assert_eq!(
+            pot.side_pots(),
+            vec![
+                SidePot {
+                    amount: 150,
+                    eligible: vec![0, 1, 2]
+                },
+                SidePot {
+                    amount: 300,
+                    eligible: vec![1, 2]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_several_all_ins_and_a_fold() {
+        let mut pot = Pot::new();
+        pot.add(0, 30).unwrap();
+        pot.add(1, 100).unwrap();
+        pot.add(2, 60).unwrap();
+        pot.add(3, 250).unwrap();
+        pot.add(4, 250).unwrap();
+        pot.fold(1);
+
+        assert_eq!(
+            pot.side_pots(),
+            vec![
+                SidePot {
+                    amount: 150,
+                    eligible: vec![0, 2, 3, 4]
+                },
+                SidePot {
+                    amount: 120,
+                    eligible: vec![2, 3, 4]
+                },
+                SidePot {
+                    amount: 420,
+                    eligible: vec![3, 4]
+                },
+            ]
+        );
+        assert_eq!(pot.total(), 690);
+    }
+
+    #[test]
+    fn test_chips_from_folded_players_go_to_the_seats_left() {
+        let mut pot = Pot::new();
+        pot.add(0, 0).unwrap();
+        pot.add(1, 25).unwrap();
+        pot.add(2, 50).unwrap();
+        pot.fold(1);
+        pot.fold(2);
+
+        assert_eq!(
+            pot.side_pots(),
+            vec![SidePot {
+                amount: 75,
+                eligible: vec![0]
+            }]
+        );
+        let report = pot.pay_out(|seat| seat, &[0, 1, 2]);
+        assert_eq!(report.total(), pot.total());
+        assert_eq!(report.amount(0), 75);
+    }
+
+    #[test]
+    fn test_split_pot_odd_chip_left_of_button() {
+        let mut pot = Pot::new();
+        pot.add(0, 25).unwrap();
+        pot.add(1, 25).unwrap();
+        pot.add(2, 25).unwrap();
+        pot.fold(1);
+
+        let order = OddChipRule::LeftOfButton.seat_order(1, 3, |_| None);
+        assert_eq!(order, vec![2, 0, 1]);
+
+        let report = pot.pay_out(|_| 1, &order);
+        assert_eq!(report.amount(2), 38);
+        assert_eq!(report.amount(0), 37);
+        assert_eq!(report.amount(1), 0);
+        assert_eq!(report.total(), 75);
+        assert_eq!(report.winners(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_split_pot_odd_chip_highest_card() {
+        use crate::card::{Rank, Suit};
+
+        let mut pot = Pot::new();
+        pot.add(0, 50).unwrap();
+        pot.add(1, 50).unwrap();
+        pot.add(2, 1).unwrap();
+        pot.fold(2);
+
+        let high_cards = [
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::Two, Suit::Clubs),
+        ];
+        let order = OddChipRule::HighestCard.seat_order(0, 3, |seat| Some(high_cards[seat]));
+        assert_eq!(order, vec![1, 0, 2]);
+
+        let report = pot.pay_out(|_| 1, &order);
+        assert_eq!(report.amount(1), 51);
+        assert_eq!(report.amount(0), 50);
+    }
+
+    #[test]
+    fn test_side_pots_paid_to_their_own_winners() {
+        let mut pot = Pot::new();
+        pot.add(0, 50).unwrap();
+        pot.add(1, 200).unwrap();
+        pot.add(2, 200).unwrap();
+
+        let strengths = [3, 2, 1];
+        let report = pot.pay_out(|seat| strengths[seat], &[0, 1, 2]);
+
+        assert_eq!(report.amount(0), 150);
+        assert_eq!(report.amount(1), 300);
+        assert_eq!(report.amount(2), 0);
+        assert_eq!(report.awards.len(), 2);
+    }
+
+    #[test]
+    fn test_high_hand_scoops_without_a_low() {
+        let mut pot = Pot::new();
+        pot.add(0, 100).unwrap();
+        pot.add(1, 100).unwrap();
+
+        let report = pot.pay_out_split(|seat| [2, 1][seat], |_| None::<u8>, &[0, 1]);
+        assert_eq!(report.amount(0), 200);
+        assert_eq!(report.awards.len(), 1);
+    }
+
+    #[test]
+    fn test_high_and_low_split_with_odd_chip_to_high() {
+        let mut pot = Pot::new();
+        pot.add(0, 50).unwrap();
+        pot.add(1, 50).unwrap();
+        pot.add(2, 25).unwrap();
+        pot.fold(2);
+
+        let lows = [None, Some(1), Some(2)];
+        let report = pot.pay_out_split(|seat| [2, 1, 3][seat], |seat| lows[seat], &[0, 1, 2]);
+        assert_eq!(report.amount(0), 63);
+        assert_eq!(report.amount(1), 62);
+        assert_eq!(report.amount(2), 0);
+        assert_eq!(report.awards.len(), 2);
+    }
+
+    #[test]
+    fn test_quartered_low() {
+        let mut pot = Pot::new();
+        for seat in 0..3 {
+            pot.add(seat, 400).unwrap();
+        }
+
+        let lows = [Some(5), Some(5), None];
+        let report = pot.pay_out_split(|seat| [3, 1, 2][seat], |seat| lows[seat], &[0, 1, 2]);
+        assert_eq!(report.amount(0), 900);
+        assert_eq!(report.amount(1), 300);
+        assert_eq!(report.amount(2), 0);
+    }
+
+    #[test]
+    fn test_uncalled_bet_goes_back_to_bettor() {
+        let mut pot = Pot::new();
+        pot.add(0, 40).unwrap();
+        pot.add(1, 500).unwrap();
+
+        let pots = pot.side_pots();
+        assert_eq!(pots[1].amount, 460);
+        assert_eq!(pots[1].eligible, vec![1]);
     }
 
-    pub fn add(&mut self, amount: u32) {
-        self.total += amount;
+    #[test]
+    fn test_pot_rejects_overflow() {
+        let mut pot = Pot::new();
+        pot.add(0, u32::MAX - 10).unwrap();
+        assert_eq!(pot.add(1, 11), Err(ChipError::Overflow));
+        assert_eq!(pot.total(), u32::MAX - 10);
+        assert_eq!(pot.add(1, 10), Ok(()));
     }
 }
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Suit {
@@ -25,7 +26,36 @@ pub enum Rank {
     Ace,
 }
 
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+    pub fn tie_break_rank(&self) -> u8 {
+        match *self {
+            Suit::Clubs => 0,
+            Suit::Diamonds => 1,
+            Suit::Hearts => 2,
+            Suit::Spades => 3,
+        }
+    }
+}
+
 impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
     pub fn as_str(&self) -> &str {
         match *self {
             Rank::Two => "2",
@@ -43,6 +73,14 @@ impl Rank {
             Rank::Ace => "A",
         }
     }
+
+    // Low hands count the ace as one, below the deuce.
+    pub fn ace_low_value(&self) -> u8 {
+        match self {
+            Rank::Ace => 1,
+            rank => *rank as u8 + 2,
+        }
+    }
 }
 
 impl fmt::Display for Rank {
@@ -78,6 +116,62 @@ impl fmt::Display for Suit {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseCardError {
+    InvalidRank(String),
+    InvalidSuit(String),
+    MissingSuit(String),
+    DuplicateCard(Card),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCardError::InvalidRank(text) => write!(f, "'{}' is not a rank", text),
+            ParseCardError::InvalidSuit(text) => write!(f, "'{}' is not a suit", text),
+            ParseCardError::MissingSuit(text) => write!(f, "'{}' is missing a suit", text),
+            ParseCardError::DuplicateCard(card) => write!(f, "{} appears more than once", card),
+        }
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_uppercase().as_str() {
+            "2" => Ok(Rank::Two),
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "10" | "T" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            "A" => Ok(Rank::Ace),
+            _ => Err(ParseCardError::InvalidRank(text.to_string())),
+        }
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "h" | "H" | "♥" | "♡" => Ok(Suit::Hearts),
+            "d" | "D" | "♦" | "♢" => Ok(Suit::Diamonds),
+            "c" | "C" | "♣" | "♧" => Ok(Suit::Clubs),
+            "s" | "S" | "♠" | "♤" => Ok(Suit::Spades),
+            _ => Err(ParseCardError::InvalidSuit(text.to_string())),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Card {
     rank: Rank,
@@ -96,6 +190,20 @@ impl Card {
     pub fn get_suit(&self) -> Suit {
         self.suit
     }
+
+    pub fn index(&self) -> u8 {
+        self.suit as u8 * 13 + self.rank as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<Card> {
+        if index >= 52 {
+            return None;
+        }
+        Some(Card::new(
+            Rank::ALL[(index % 13) as usize],
+            Suit::ALL[(index / 13) as usize],
+        ))
+    }
 }
 
 impl fmt::Display for Card {
@@ -104,30 +212,205 @@ impl fmt::Display for Card {
     }
 }
 
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let (split, _) = text
+            .char_indices()
+            .last()
+            .filter(|&(split, _)| split > 0)
+            .ok_or_else(|| ParseCardError::MissingSuit(text.to_string()))?;
+
+        Ok(Card::new(text[..split].parse()?, text[split..].parse()?))
+    }
+}
+
+pub fn parse_cards(text: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut cards = Vec::new();
+    let mut seen = CardSet::new();
+    let mut chars = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, ',' | '[' | ']'))
+        .peekable();
+
+    while let Some(first) = chars.next() {
+        let mut rank = first.to_string();
+        if first == '1' && chars.peek() == Some(&'0') {
+            rank.push(chars.next().unwrap_or('0'));
+        }
+        let suit = chars
+            .next()
+            .ok_or_else(|| ParseCardError::MissingSuit(rank.clone()))?;
+
+        let card = Card::new(rank.parse()?, suit.to_string().parse()?);
+        if !seen.insert(card) {
+            return Err(ParseCardError::DuplicateCard(card));
+        }
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    pub const FULL: CardSet = CardSet((1 << 52) - 1);
+
+    pub fn new() -> Self {
+        Self::EMPTY
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        CardSet(bits & Self::FULL.0)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & (1 << card.index()) != 0
+    }
+
+    pub fn insert(&mut self, card: Card) -> bool {
+        let inserted = !self.contains(card);
+        self.0 |= 1 << card.index();
+        inserted
+    }
+
+    pub fn remove(&mut self, card: Card) -> bool {
+        let removed = self.contains(card);
+        self.0 &= !(1 << card.index());
+        removed
+    }
+
+    pub fn union(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(&self, other: CardSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub fn rank_mask(&self, suit: Suit) -> u16 {
+        ((self.0 >> (suit as u64 * 13)) & 0x1fff) as u16
+    }
+
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Card::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.0.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+        let mut set = CardSet::new();
+        set.extend(cards);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.insert(card);
+        }
+    }
+}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> Self {
+        CardSet(1 << card.index())
+    }
+}
+
+impl FromStr for CardSet {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(parse_cards(text)?.into_iter().collect())
+    }
+}
+
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards = self
+            .iter()
+            .map(|card| card.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "[{}]", cards)
+    }
+}
+
+#[derive(Clone)]
 pub struct Deck {
     cards: Vec<Card>,
 }
 
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Deck {
     pub fn new() -> Self {
         let mut cards = Vec::new();
 
-        for suit in &[Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades] {
-            for rank in &[
-                Rank::Two,
-                Rank::Three,
-                Rank::Four,
-                Rank::Five,
-                Rank::Six,
-                Rank::Seven,
-                Rank::Eight,
-                Rank::Nine,
-                Rank::Ten,
-                Rank::Jack,
-                Rank::Queen,
-                Rank::King,
-                Rank::Ace,
-            ] {
+        for suit in &Suit::ALL {
+            for rank in &Rank::ALL {
                 cards.push(Card::new(*rank, *suit));
             }
         }
@@ -136,17 +419,40 @@ impl Deck {
     }
 
     pub fn shuffle(&mut self) {
-        use rand::seq::SliceRandom;
         use rand::thread_rng;
 
         let mut rng = thread_rng();
-        self.cards.shuffle(&mut rng);
+        self.shuffle_with(&mut rng);
+    }
+
+    pub fn shuffle_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+        use rand::seq::SliceRandom;
+
+        self.cards.shuffle(rng);
+    }
+
+    pub fn shuffle_seeded(&mut self, seed: u64) {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        self.shuffle_with(&mut rng);
     }
 
     pub fn draw(&mut self) -> Option<Card> {
         self.cards.pop()
     }
 
+    pub fn remove(&mut self, card: Card) -> bool {
+        let before = self.cards.len();
+        self.cards.retain(|&other| other != card);
+        self.cards.len() < before
+    }
+
+    pub fn add_to_bottom(&mut self, cards: Vec<Card>) {
+        self.cards.splice(0..0, cards);
+    }
+
     pub fn remaining_cards(&self) -> usize {
         self.cards.len()
     }
@@ -194,12 +500,46 @@ mod tests {
         assert_that!(card, not(equal_to(card2)));
     }
 
+    #[test]
+    fn test_ace_low_value() {
+        assert_that!(Rank::Ace.ace_low_value(), equal_to(1));
+        assert_that!(Rank::Two.ace_low_value(), equal_to(2));
+        assert_that!(Rank::King.ace_low_value(), equal_to(13));
+    }
+
     #[test]
     fn test_deck_size() {
         let deck = Deck::new();
         assert_that!(deck.cards.len(), equal_to(52));
     }
 
+    #[test]
+    fn test_remove_card_from_deck() {
+        let mut deck = Deck::new();
+        let card = Card::new(Rank::Ace, Suit::Spades);
+
+        assert_that!(deck.remove(card), equal_to(true));
+        assert_that!(deck.remove(card), equal_to(false));
+        assert_that!(deck.remaining_cards(), equal_to(51));
+    }
+
+    #[test]
+    fn test_cards_added_to_the_bottom_are_dealt_last() {
+        let mut deck = Deck::new();
+        deck.deal(50).unwrap();
+        let stub = deck.deal(2).unwrap();
+        let muck = vec![
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Clubs),
+        ];
+        deck.add_to_bottom(stub.clone());
+        deck.add_to_bottom(muck.clone());
+
+        assert_that!(deck.remaining_cards(), equal_to(4));
+        assert_that!(deck.deal(2).unwrap(), equal_to(stub));
+        assert_that!(deck.deal(2).unwrap(), equal_to(muck));
+    }
+
     #[test]
     fn test_deal_to_players() {
         let mut deck = Deck::new();
@@ -209,4 +549,175 @@ mod tests {
         assert_that!(hands.len(), equal_to(4));
         assert_that!(hands[0].len(), equal_to(5));
     }
+
+    #[test]
+    fn test_card_index_round_trip() {
+        let deck = Deck::new();
+        for card in deck.cards.iter() {
+            assert_that!(Card::from_index(card.index()), equal_to(Some(*card)));
+        }
+        assert_that!(Card::new(Rank::Two, Suit::Hearts).index(), equal_to(0));
+        assert_that!(Card::new(Rank::Ace, Suit::Spades).index(), equal_to(51));
+        assert_that!(Card::from_index(52), equal_to(None));
+    }
+
+    #[test]
+    fn test_card_set_operations() {
+        let ace = Card::new(Rank::Ace, Suit::Spades);
+        let king = Card::new(Rank::King, Suit::Hearts);
+        let two = Card::new(Rank::Two, Suit::Clubs);
+
+        let mut set = CardSet::new();
+        assert_that!(set.is_empty(), is(true));
+        assert_that!(set.insert(ace), is(true));
+        assert_that!(set.insert(ace), is(false));
+        set.insert(king);
+        assert_that!(set.len(), equal_to(2));
+        assert_that!(set.contains(king), is(true));
+        assert_that!(set.contains(two), is(false));
+
+        let other: CardSet = [king, two].into_iter().collect();
+        assert_that!(set.union(other).len(), equal_to(3));
+        assert_that!(set.intersection(other), equal_to(CardSet::from(king)));
+        assert_that!(set.difference(other), equal_to(CardSet::from(ace)));
+        assert_that!(set.is_disjoint(CardSet::from(two)), is(true));
+
+        assert_that!(set.remove(ace), is(true));
+        assert_that!(set.remove(ace), is(false));
+        assert_that!(set, equal_to(CardSet::from(king)));
+    }
+
+    #[test]
+    fn test_card_set_iteration_and_rank_masks() {
+        let deck = Deck::new();
+        let full: CardSet = deck.cards.iter().copied().collect();
+        assert_that!(full, equal_to(CardSet::FULL));
+        assert_that!(full.iter().len(), equal_to(52));
+        assert_that!(full.rank_mask(Suit::Clubs), equal_to(0x1fff));
+
+        let hand: CardSet = [
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Two, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Hearts),
+        ]
+        .into_iter()
+        .collect();
+        assert_that!(hand.rank_mask(Suit::Spades), equal_to(0b1_0000_0000_0001));
+        assert_that!(hand.rank_mask(Suit::Diamonds), equal_to(0));
+        assert_that!(
+            hand.iter().collect::<Vec<_>>(),
+            equal_to(vec![
+                Card::new(Rank::Ten, Suit::Hearts),
+                Card::new(Rank::Two, Suit::Spades),
+                Card::new(Rank::Ace, Suit::Spades),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_rank_and_suit() {
+        assert_that!("A".parse::<Rank>(), equal_to(Ok(Rank::Ace)));
+        assert_that!("t".parse::<Rank>(), equal_to(Ok(Rank::Ten)));
+        assert_that!("10".parse::<Rank>(), equal_to(Ok(Rank::Ten)));
+        assert_that!("7".parse::<Rank>(), equal_to(Ok(Rank::Seven)));
+        assert_that!(
+            "1".parse::<Rank>(),
+            equal_to(Err(ParseCardError::InvalidRank("1".to_string())))
+        );
+
+        assert_that!("s".parse::<Suit>(), equal_to(Ok(Suit::Spades)));
+        assert_that!("D".parse::<Suit>(), equal_to(Ok(Suit::Diamonds)));
+        assert_that!("♥".parse::<Suit>(), equal_to(Ok(Suit::Hearts)));
+        assert_that!("♣".parse::<Suit>(), equal_to(Ok(Suit::Clubs)));
+        assert_that!(
+            "x".parse::<Suit>(),
+            equal_to(Err(ParseCardError::InvalidSuit("x".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_that!(
+            "As".parse::<Card>(),
+            equal_to(Ok(Card::new(Rank::Ace, Suit::Spades)))
+        );
+        assert_that!(
+            "Td".parse::<Card>(),
+            equal_to(Ok(Card::new(Rank::Ten, Suit::Diamonds)))
+        );
+        assert_that!(
+            "10h".parse::<Card>(),
+            equal_to(Ok(Card::new(Rank::Ten, Suit::Hearts)))
+        );
+        assert_that!(
+            "Q♦".parse::<Card>(),
+            equal_to(Ok(Card::new(Rank::Queen, Suit::Diamonds)))
+        );
+        assert_that!(
+            "A".parse::<Card>(),
+            equal_to(Err(ParseCardError::MissingSuit("A".to_string())))
+        );
+
+        for card in Deck::new().cards {
+            assert_that!(card.to_string().parse::<Card>(), equal_to(Ok(card)));
+        }
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        let cards = parse_cards("AsKd 7c8c9c").unwrap();
+        assert_that!(
+            cards,
+            equal_to(vec![
+                Card::new(Rank::Ace, Suit::Spades),
+                Card::new(Rank::King, Suit::Diamonds),
+                Card::new(Rank::Seven, Suit::Clubs),
+                Card::new(Rank::Eight, Suit::Clubs),
+                Card::new(Rank::Nine, Suit::Clubs),
+            ])
+        );
+
+        let displayed = parse_cards("[10♥, 4♣, K♠]").unwrap();
+        assert_that!(displayed.len(), equal_to(3));
+        assert_that!(displayed[0], equal_to(Card::new(Rank::Ten, Suit::Hearts)));
+
+        assert_that!(
+            parse_cards("AsAs"),
+            equal_to(Err(ParseCardError::DuplicateCard(Card::new(
+                Rank::Ace,
+                Suit::Spades
+            ))))
+        );
+        assert_that!(
+            parse_cards("AsK"),
+            equal_to(Err(ParseCardError::MissingSuit("K".to_string())))
+        );
+        assert_that!(
+            "2c 3c".parse::<CardSet>().map(|set| set.len()),
+            equal_to(Ok(2))
+        );
+    }
+
+    #[test]
+    fn test_seeded_shuffle_is_reproducible() {
+        let mut first = Deck::new();
+        let mut second = Deck::new();
+        first.shuffle_seeded(42);
+        second.shuffle_seeded(42);
+        assert_that!(&first.cards, equal_to(&second.cards));
+
+        let mut other = Deck::new();
+        other.shuffle_seeded(43);
+        assert_that!(&first.cards, not(equal_to(&other.cards)));
+    }
+
+    #[test]
+    fn test_seeded_shuffle_is_stable() {
+        let mut deck = Deck::new();
+        deck.shuffle_seeded(42);
+        assert_that!(
+            deck.deal(5).unwrap(),
+            equal_to(parse_cards("K♣ 9♠ 9♥ 10♣ K♥").unwrap())
+        );
+    }
 }
@@ -0,0 +1,360 @@
+use crate::betting::BlindStructure;
+use crate::game::Game;
+use crate::player::Player;
+use crate::table::{Table, TableError, MAX_SEATS, MIN_SEATS};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirectorError {
+    InvalidTableSize(usize),
+    NotEnoughPlayers,
+    NoSuchTable(usize),
+    HandInProgress(usize),
+    Table(TableError),
+}
+
+impl fmt::Display for DirectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DirectorError::InvalidTableSize(size) => write!(
+                f,
+                "Tables need between {} and {} seats, not {}",
+                MIN_SEATS, MAX_SEATS, size
+            ),
+            DirectorError::NotEnoughPlayers => write!(f, "At least two players are needed"),
+            DirectorError::NoSuchTable(table) => write!(f, "Table {} does not exist", table),
+            DirectorError::HandInProgress(table) => {
+                write!(f, "A hand is still in progress at table {}", table)
+            }
+            DirectorError::Table(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<TableError> for DirectorError {
+    fn from(error: TableError) -> Self {
+        DirectorError::Table(error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move<'a> {
+    pub name: &'a str,
+    pub from_table: usize,
+    pub from_seat: usize,
+    pub to_table: usize,
+    pub to_seat: usize,
+}
+
+pub struct TournamentDirector<'a> {
+    games: BTreeMap<usize, Game<'a>>,
+    seats_per_table: usize,
+}
+
+impl<'a> TournamentDirector<'a> {
+    pub fn new(players: Vec<Player<'a>>, seats_per_table: usize) -> Result<Self, DirectorError> {
+        if !(MIN_SEATS..=MAX_SEATS).contains(&seats_per_table) {
+            return Err(DirectorError::InvalidTableSize(seats_per_table));
+        }
+        if players.len() < 2 {
+            return Err(DirectorError::NotEnoughPlayers);
+        }
+
+        let table_count = players.len().div_ceil(seats_per_table);
+        let mut tables = (0..table_count)
+            .map(|_| Table::new(seats_per_table))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (index, player) in players.into_iter().enumerate() {
+            tables[index % table_count].sit_anywhere(player)?;
+        }
+
+        Ok(Self {
+            games: tables.into_iter().map(Game::new).enumerate().collect(),
+            seats_per_table,
+        })
+    }
+
+    pub fn table_ids(&self) -> Vec<usize> {
+        self.games.keys().copied().collect()
+    }
+
+    pub fn game(&self, table: usize) -> Option<&Game<'a>> {
+        self.games.get(&table)
+    }
+
+    pub fn game_mut(&mut self, table: usize) -> Option<&mut Game<'a>> {
+        self.games.get_mut(&table)
+    }
+
+    pub fn table_sizes(&self) -> Vec<(usize, usize)> {
+        self.games
+            .iter()
+            .map(|(&id, game)| (id, game.table().occupied_seats().count()))
+            .collect()
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.table_sizes().iter().map(|&(_, size)| size).sum()
+    }
+
+    pub fn is_final_table(&self) -> bool {
+        self.games.len() == 1
+    }
+
+    fn check_hands_over(&self) -> Result<(), DirectorError> {
+        match self
+            .games
+            .iter()
+            .find_map(|(id, game)| (!game.is_hand_over()).then_some(id))
+        {
+            Some(&id) => Err(DirectorError::HandInProgress(id)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn set_blinds(&mut self, blinds: BlindStructure) {
+        for game in self.games.values_mut() {
+            game.set_blinds(blinds);
+        }
+    }
+
+    // Every table has to be between hands before any player is removed,
+    // so a busy table never leaves busted players half taken out.
+    pub fn remove_busted(&mut self) -> Result<Vec<(usize, Player<'a>)>, DirectorError> {
+        self.check_hands_over()?;
+
+        let mut busted = Vec::new();
+        for (&id, game) in self.games.iter_mut() {
+            let table = game
+                .table_mut()
+                .map_err(|_| DirectorError::HandInProgress(id))?;
+            let seats: Vec<usize> = table
+                .players()
+                .filter(|(_, player)| player.is_busted())
+                .map(|(seat, _)| seat)
+                .collect();
+            for seat in seats {
+                busted.push((id, table.leave(seat)?));
+            }
+        }
+        Ok(busted)
+    }
+
+    pub fn balance(&mut self) -> Result<Vec<Move<'a>>, DirectorError> {
+        self.check_hands_over()?;
+
+        let mut moves = Vec::new();
+        let tables_needed = self.player_count().div_ceil(self.seats_per_table).max(1);
+        while self.games.len() > tables_needed {
+            let (broken, _) = self.smallest_table(None);
+            let seats: Vec<usize> = self.games[&broken].table().occupied_seats().collect();
+            for seat in seats {
+                let (to, _) = self.smallest_table(Some(broken));
+                moves.push(self.move_player(broken, seat, to)?);
+            }
+            self.games.remove(&broken);
+        }
+
+        loop {
+            let (largest, most) = self.largest_table();
+            let (smallest, fewest) = self.smallest_table(None);
+            if most - fewest <= 1 {
+                break;
+            }
+            let seat = self.seat_to_move(largest);
+            moves.push(self.move_player(largest, seat, smallest)?);
+        }
+
+        Ok(moves)
+    }
+
+    fn largest_table(&self) -> (usize, usize) {
+        self.table_sizes()
+            .into_iter()
+            .max_by_key(|&(id, size)| (size, std::cmp::Reverse(id)))
+            .expect("The director always has a table")
+    }
+
+    fn smallest_table(&self, except: Option<usize>) -> (usize, usize) {
+        self.table_sizes()
+            .into_iter()
+            .filter(|&(id, _)| Some(id) != except)
+            .min_by_key(|&(id, size)| (size, std::cmp::Reverse(id)))
+            .expect("The director always has a table")
+    }
+
+    fn seat_to_move(&self, table: usize) -> usize {
+        let table = self.games[&table].table();
+        let next_big_blind = table
+            .positions()
+            .and_then(|positions| table.next_active(positions.big_blind));
+        next_big_blind
+            .or_else(|| table.occupied_seats().last())
+            .expect("Players only move from occupied tables")
+    }
+
+    fn move_player(
+        &mut self,
+        from_table: usize,
+        from_seat: usize,
+        to_table: usize,
+    ) -> Result<Move<'a>, DirectorError> {
+        let player = self
+            .games
+            .get_mut(&from_table)
+            .ok_or(DirectorError::NoSuchTable(from_table))?
+            .table_mut()
+            .map_err(|_| DirectorError::HandInProgress(from_table))?
+            .leave(from_seat)?;
+        let name = player.name;
+        let to_seat = self
+            .games
+            .get_mut(&to_table)
+            .ok_or(DirectorError::NoSuchTable(to_table))?
+            .table_mut()
+            .map_err(|_| DirectorError::HandInProgress(to_table))?
+            .sit_anywhere(player)?;
+
+        Ok(Move {
+            name,
+            from_table,
+            from_seat,
+            to_table,
+            to_seat,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::betting::Action;
+
+    const NAMES: [&str; 27] = [
+        "Dusan", "Ana", "Marko", "Jelena", "Nikola", "Ivana", "Stefan", "Milica", "Luka", "Sara",
+        "Petar", "Teodora", "Filip", "Mina", "Vuk", "Nina", "Lazar", "Maja", "Ognjen", "Tara",
+        "Bojan", "Jovana", "Aleksa", "Katarina", "Milos", "Dragana", "Uros",
+    ];
+
+    fn new_director(count: usize) -> TournamentDirector<'static> {
+        let players = NAMES[..count]
+            .iter()
+            .map(|name| Player::new(name, 1000))
+            .collect();
+        TournamentDirector::new(players, 9).unwrap()
+    }
+
+    fn bust(director: &mut TournamentDirector, count: usize) {
+        let mut left = count;
+        for id in director.table_ids() {
+            let table = director.game_mut(id).unwrap().table_mut().unwrap();
+            let seats: Vec<usize> = table.occupied_seats().collect();
+            for seat in seats {
+                if left == 0 {
+                    return;
+                }
+                table.player_mut(seat).unwrap().chips = 0;
+                left -= 1;
+            }
+        }
+    }
+
+    fn sizes(director: &TournamentDirector) -> Vec<usize> {
+        director
+            .table_sizes()
+            .into_iter()
+            .map(|(_, size)| size)
+            .collect()
+    }
+
+    #[test]
+    fn test_seats_players_evenly() {
+        let director = new_director(20);
+        assert_eq!(sizes(&director), vec![7, 7, 6]);
+        assert_eq!(director.player_count(), 20);
+        assert!(!director.is_final_table());
+
+        assert!(matches!(
+            TournamentDirector::new(vec![Player::new("Dusan", 100)], 9),
+            Err(DirectorError::NotEnoughPlayers)
+        ));
+        assert!(matches!(
+            TournamentDirector::new(vec![], 11),
+            Err(DirectorError::InvalidTableSize(11))
+        ));
+    }
+
+    #[test]
+    fn test_moves_players_to_keep_tables_within_one() {
+        let mut director = new_director(27);
+        bust(&mut director, 4);
+        assert_eq!(director.remove_busted().unwrap().len(), 4);
+        assert_eq!(sizes(&director), vec![5, 9, 9]);
+
+        let moves = director.balance().unwrap();
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().all(|step| step.to_table == 0));
+        assert_eq!(sizes(&director), vec![7, 8, 8]);
+        assert_eq!(director.player_count(), 23);
+    }
+
+    #[test]
+    fn test_breaks_tables_down_to_the_final_table() {
+        let mut director = new_director(20);
+        bust(&mut director, 2);
+        director.remove_busted().unwrap();
+
+        let moves = director.balance().unwrap();
+        assert_eq!(moves.len(), 5);
+        assert!(moves.iter().all(|step| step.from_table == 0));
+        assert_eq!(director.table_ids(), vec![1, 2]);
+        assert_eq!(sizes(&director), vec![9, 9]);
+
+        bust(&mut director, 9);
+        director.remove_busted().unwrap();
+        director.balance().unwrap();
+        assert!(director.is_final_table());
+        assert_eq!(director.player_count(), 9);
+    }
+
+    #[test]
+    fn test_moves_the_player_due_for_the_big_blind() {
+        let mut director = new_director(12);
+        let game = director.game_mut(1).unwrap();
+        game.start_hand().unwrap();
+        while !game.is_hand_over() {
+            game.act(Action::Fold).unwrap();
+        }
+        let big_blind = game.positions().unwrap().big_blind;
+
+        bust(&mut director, 2);
+        director.remove_busted().unwrap();
+        let moves = director.balance().unwrap();
+
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].from_table, 1);
+        assert_eq!(moves[0].from_seat, big_blind + 1);
+    }
+
+    #[test]
+    fn test_waits_for_hands_to_finish() {
+        let mut director = new_director(12);
+        director.game_mut(0).unwrap().start_hand().unwrap();
+        assert_eq!(director.balance(), Err(DirectorError::HandInProgress(0)));
+    }
+
+    #[test]
+    fn test_remove_busted_leaves_everyone_seated_while_a_hand_runs() {
+        let mut director = new_director(12);
+        bust(&mut director, 1);
+        let last = *director.table_ids().last().unwrap();
+        director.game_mut(last).unwrap().start_hand().unwrap();
+
+        assert_eq!(
+            director.remove_busted().map(|busted| busted.len()),
+            Err(DirectorError::HandInProgress(last))
+        );
+        assert_eq!(director.player_count(), 12);
+    }
+}
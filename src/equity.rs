@@ -0,0 +1,442 @@
+use crate::card::{Card, CardSet, Deck};
+use crate::hand_eval::HandValue;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 10;
+
+// Every split of a pot between 1 and 10 players is a whole number of these.
+const SHARE_UNIT: u64 = 2520;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EquityError {
+    InvalidPlayerCount(usize),
+    BoardTooLong(usize),
+    DuplicateCard(Card),
+    NoIterations,
+    NotEnoughCards { needed: usize, available: usize },
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::InvalidPlayerCount(count) => write!(
+                f,
+                "Equity needs between {} and {} players, not {}",
+                MIN_PLAYERS, MAX_PLAYERS, count
+            ),
+            EquityError::BoardTooLong(count) => {
+                write!(f, "A board has at most 5 cards, not {}", count)
+            }
+            EquityError::DuplicateCard(card) => write!(f, "{} is used more than once", card),
+            EquityError::NoIterations => write!(f, "At least one iteration is needed"),
+            EquityError::NotEnoughCards { needed, available } => write!(
+                f,
+                "The board needs {} more cards but only {} are left",
+                needed, available
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Equity {
+    trials: u64,
+    wins: u64,
+    ties: u64,
+    share: u64,
+}
+
+impl Equity {
+    pub fn trials(&self) -> u64 {
+        self.trials
+    }
+
+    pub fn wins(&self) -> u64 {
+        self.wins
+    }
+
+    pub fn ties(&self) -> u64 {
+        self.ties
+    }
+
+    pub fn losses(&self) -> u64 {
+        self.trials - self.wins - self.ties
+    }
+
+    pub fn win_percent(&self) -> f64 {
+        self.percent(self.wins)
+    }
+
+    pub fn tie_percent(&self) -> f64 {
+        self.percent(self.ties)
+    }
+
+    pub fn lose_percent(&self) -> f64 {
+        self.percent(self.losses())
+    }
+
+    pub fn equity(&self) -> f64 {
+        if self.trials == 0 {
+            return 0.0;
+        }
+        self.share as f64 / (self.trials * SHARE_UNIT) as f64
+    }
+
+    fn percent(&self, count: u64) -> f64 {
+        if self.trials == 0 {
+            return 0.0;
+        }
+        count as f64 * 100.0 / self.trials as f64
+    }
+}
+
+impl fmt::Display for Equity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "win {:.2}%, tie {:.2}%, lose {:.2}%, equity {:.2}%",
+            self.win_percent(),
+            self.tie_percent(),
+            self.lose_percent(),
+            self.equity() * 100.0
+        )
+    }
+}
+
+pub struct EquityCalculator {
+    hands: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+    iterations: u64,
+    seed: u64,
+}
+
+impl EquityCalculator {
+    pub fn new(hands: Vec<[Card; 2]>) -> Self {
+        Self {
+            hands,
+            board: Vec::new(),
+            dead: Vec::new(),
+            iterations: 10_000,
+            seed: rand::random(),
+        }
+    }
+
+    pub fn set_board(&mut self, board: Vec<Card>) {
+        self.board = board;
+    }
+
+    pub fn set_dead(&mut self, dead: Vec<Card>) {
+        self.dead = dead;
+    }
+
+    pub fn set_iterations(&mut self, iterations: u64) {
+        self.iterations = iterations;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub fn monte_carlo(&self) -> Result<Vec<Equity>, EquityError> {
+        let known = self.validate()?;
+        if self.iterations == 0 {
+            return Err(EquityError::NoIterations);
+        }
+
+        let mut stub = Deck::new();
+        for card in known {
+            stub.remove(card);
+        }
+
+        let hands = self.hand_sets();
+        let board: CardSet = self.board.iter().copied().collect();
+        let missing = 5 - self.board.len();
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut results = vec![Equity::default(); hands.len()];
+
+        for _ in 0..self.iterations {
+            let mut deck = stub.clone();
+            deck.shuffle_with(&mut rng);
+            let runout = deck
+                .deal(missing)
+                .expect("Ten hands and a board always leave enough cards");
+            let full_board = board.union(runout.into_iter().collect());
+            record_showdown(&hands, full_board, &mut results);
+        }
+
+        Ok(results)
+    }
+
+    pub fn exact(&self) -> Result<Vec<Equity>, EquityError> {
+        let known = self.validate()?;
+        let hands = self.hand_sets();
+        let board: CardSet = self.board.iter().copied().collect();
+        let stub: Vec<CardSet> = CardSet::FULL
+            .difference(known)
+            .into_iter()
+            .map(CardSet::from)
+            .collect();
+        let mut results = vec![Equity::default(); hands.len()];
+
+        enumerate_runouts(&stub, 5 - self.board.len(), board, &hands, &mut results);
+        Ok(results)
+    }
+
+    fn hand_sets(&self) -> Vec<CardSet> {
+        self.hands
+            .iter()
+            .map(|hand| hand.iter().copied().collect())
+            .collect()
+    }
+
+    fn validate(&self) -> Result<CardSet, EquityError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&self.hands.len()) {
+            return Err(EquityError::InvalidPlayerCount(self.hands.len()));
+        }
+        if self.board.len() > 5 {
+            return Err(EquityError::BoardTooLong(self.board.len()));
+        }
+
+        let mut known = CardSet::new();
+        let cards = self
+            .hands
+            .iter()
+            .flatten()
+            .chain(&self.board)
+            .chain(&self.dead);
+        for &card in cards {
+            if !known.insert(card) {
+                return Err(EquityError::DuplicateCard(card));
+            }
+        }
+
+        let needed = 5 - self.board.len();
+        let available = 52 - known.len();
+        if available < needed {
+            return Err(EquityError::NotEnoughCards { needed, available });
+        }
+        Ok(known)
+    }
+}
+
+fn enumerate_runouts(
+    stub: &[CardSet],
+    missing: usize,
+    board: CardSet,
+    hands: &[CardSet],
+    results: &mut [Equity],
+) {
+    if missing == 0 {
+        record_showdown(hands, board, results);
+        return;
+    }
+
+    for (index, &card) in stub.iter().enumerate().take(stub.len() + 1 - missing) {
+        enumerate_runouts(
+            &stub[index + 1..],
+            missing - 1,
+            board.union(card),
+            hands,
+            results,
+        );
+    }
+}
+
+pub(crate) fn record_showdown(hands: &[CardSet], board: CardSet, results: &mut [Equity]) {
+    let mut values = [HandValue::evaluate_set(board.union(hands[0])); MAX_PLAYERS];
+    for (value, hand) in values.iter_mut().zip(hands).skip(1) {
+        *value = HandValue::evaluate_set(board.union(*hand));
+    }
+    let values = &values[..hands.len()];
+
+    let best = *values
+        .iter()
+        .max()
+        .expect("Equity always compares two or more hands");
+    let winners = values.iter().filter(|&&value| value == best).count() as u64;
+
+    for (result, &value) in results.iter_mut().zip(values) {
+        result.trials += 1;
+        if value == best {
+            if winners == 1 {
+                result.wins += 1;
+            } else {
+                result.ties += 1;
+            }
+            result.share += SHARE_UNIT / winners;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn hand(text: &str) -> [Card; 2] {
+        let cards = parse_cards(text).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    fn calculator(hands: &[&str], board: &str) -> EquityCalculator {
+        let mut calculator = EquityCalculator::new(hands.iter().map(|text| hand(text)).collect());
+        calculator.set_board(parse_cards(board).unwrap());
+        calculator.set_iterations(20_000);
+        calculator.set_seed(7);
+        calculator
+    }
+
+    #[test]
+    fn test_aces_against_kings() {
+        let results = calculator(&["AsAh", "KsKh"], "").monte_carlo().unwrap();
+
+        assert!((results[0].equity() - 0.82).abs() < 0.02);
+        assert!((results[0].equity() + results[1].equity() - 1.0).abs() < 1e-9);
+        assert_eq!(results[0].wins(), results[1].losses());
+        assert_eq!(results[0].trials(), 20_000);
+    }
+
+    #[test]
+    fn test_same_seed_same_result() {
+        let first = calculator(&["AsKs", "7h7d", "QcJc"], "2s 7c Kd").monte_carlo();
+        let second = calculator(&["AsKs", "7h7d", "QcJc"], "2s 7c Kd").monte_carlo();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_complete_board_is_decided() {
+        let results = calculator(&["AsAh", "KsKh"], "Kd 7c 2h 9s 4d")
+            .monte_carlo()
+            .unwrap();
+
+        assert_eq!(results[1].win_percent(), 100.0);
+        assert_eq!(results[0].lose_percent(), 100.0);
+    }
+
+    #[test]
+    fn test_board_plays_for_everyone() {
+        let results = calculator(&["2c3d", "2h3s", "4c5d"], "As Ks Qs Js Ts")
+            .monte_carlo()
+            .unwrap();
+
+        for result in &results {
+            assert_eq!(result.tie_percent(), 100.0);
+            assert!((result.equity() - 1.0 / 3.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_dead_cards_are_not_dealt() {
+        let mut calculator = calculator(&["AsAh", "KsKh"], "");
+        calculator.set_dead(parse_cards("Kd Kc").unwrap());
+
+        let results = calculator.monte_carlo().unwrap();
+        assert!(results[0].equity() > 0.9);
+    }
+
+    #[test]
+    fn test_exact_turn_equity() {
+        let results = calculator(&["AsAh", "KsKh"], "Kd 7c 2h 9s")
+            .exact()
+            .unwrap();
+
+        assert_eq!(results[0].trials(), 44);
+        assert_eq!(results[0].wins(), 2);
+        assert_eq!(results[1].wins(), 42);
+        assert_eq!(results[1].equity(), 42.0 / 44.0);
+    }
+
+    #[test]
+    fn test_exact_flop_equity_with_split_pots() {
+        let results = calculator(&["Ah2c", "Ad3c"], "Ks Qs Jd").exact().unwrap();
+
+        assert_eq!(results[0].trials(), 990);
+        assert_eq!(results[0].ties(), results[1].ties());
+        assert_eq!(results[0].share + results[1].share, 990 * SHARE_UNIT);
+    }
+
+    #[test]
+    fn test_exact_preflop_heads_up() {
+        let results = calculator(&["AcAd", "KhKs"], "").exact().unwrap();
+
+        assert_eq!(results[0].trials(), 1_712_304);
+        assert_eq!(results[0].wins(), 1_388_072);
+        assert_eq!(results[0].ties(), 6_538);
+        assert_eq!(
+            format!("{}", results[0]),
+            "win 81.06%, tie 0.38%, lose 18.55%, equity 81.26%"
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo_agrees_with_exact() {
+        let calculator = calculator(&["AsKs", "7h7d", "QcJc"], "2s 7c Kd");
+        let exact = calculator.exact().unwrap();
+        let sampled = calculator.monte_carlo().unwrap();
+
+        for (exact, sampled) in exact.iter().zip(&sampled) {
+            assert!((exact.equity() - sampled.equity()).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert_eq!(
+            calculator(&["AsAh"], "").monte_carlo(),
+            Err(EquityError::InvalidPlayerCount(1))
+        );
+        assert_eq!(
+            calculator(&["AsAh", "AsKh"], "").monte_carlo(),
+            Err(EquityError::DuplicateCard("As".parse().unwrap()))
+        );
+        assert_eq!(
+            calculator(&["AsAh", "KsKh"], "2c 3c 4c 5c 6c 7c").monte_carlo(),
+            Err(EquityError::BoardTooLong(6))
+        );
+
+        let mut calculator = calculator(&["AsAh", "KsKh"], "");
+        calculator.set_iterations(0);
+        assert_eq!(calculator.monte_carlo(), Err(EquityError::NoIterations));
+    }
+
+    fn leave_three_cards(calculator: &mut EquityCalculator, hands: &[&str]) {
+        let held: Vec<Card> = hands.iter().flat_map(|text| hand(text)).collect();
+        let dead = Deck::new()
+            .deal(52)
+            .unwrap()
+            .into_iter()
+            .filter(|card| !held.contains(card))
+            .take(45)
+            .collect();
+        calculator.set_dead(dead);
+    }
+
+    #[test]
+    fn test_exact_rejects_too_many_dead_cards() {
+        let mut calculator = calculator(&["AsAh", "KsKh"], "");
+        leave_three_cards(&mut calculator, &["AsAh", "KsKh"]);
+        let error = Err(EquityError::NotEnoughCards {
+            needed: 5,
+            available: 3,
+        });
+        assert_eq!(calculator.exact(), error);
+        assert_eq!(calculator.monte_carlo(), error);
+    }
+
+    #[test]
+    fn test_monte_carlo_rejects_too_many_dead_cards() {
+        let mut calculator = calculator(&["AsAh", "KsKh"], "");
+        leave_three_cards(&mut calculator, &["AsAh", "KsKh"]);
+        assert_eq!(
+            calculator.monte_carlo(),
+            Err(EquityError::NotEnoughCards {
+                needed: 5,
+                available: 3
+            })
+        );
+    }
+}
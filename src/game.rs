@@ -0,0 +1,1771 @@
+use crate::betting::{
+    Action, BettingContext, BettingError, BettingStructure, BlindStructure, ForcedBet,
+    ForcedBetKind, OddChipRule, PayoutReport, Pot, Round,
+};
+use crate::card::{Card, CardSet, Deck, Rank};
+use crate::hand_eval::{
+    evaluate_best_hand, evaluate_best_low, evaluate_best_lowball, evaluate_omaha_hand,
+    evaluate_omaha_low, BestHand, LowHand, Lowball, LowballHand,
+};
+use crate::player::{ChipError, Player};
+use crate::table::{Positions, Table, TableError};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Reverse;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    BeforeDraw,
+    Draw,
+    AfterDraw,
+    Showdown,
+}
+
+impl fmt::Display for Street {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Street::Preflop => "Preflop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+            Street::Third => "Third Street",
+            Street::Fourth => "Fourth Street",
+            Street::Fifth => "Fifth Street",
+            Street::Sixth => "Sixth Street",
+            Street::Seventh => "Seventh Street",
+            Street::BeforeDraw => "Before the Draw",
+            Street::Draw => "Draw",
+            Street::AfterDraw => "After the Draw",
+            Street::Showdown => "Showdown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// The flop, turn and river each burn a card before they are dealt.
+const BOARD_AND_BURN_CARDS: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    Holdem,
+    Omaha,
+    FiveCardOmaha,
+    SixCardOmaha,
+    OmahaHiLo,
+    SevenCardStud,
+    SevenCardStudHiLo,
+    FiveCardDraw,
+    DeuceToSevenTripleDraw,
+    Razz,
+}
+
+impl Variant {
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            Variant::Holdem => 2,
+            Variant::Omaha | Variant::OmahaHiLo => 4,
+            Variant::FiveCardOmaha | Variant::FiveCardDraw | Variant::DeuceToSevenTripleDraw => 5,
+            Variant::SixCardOmaha => 6,
+            Variant::SevenCardStud | Variant::SevenCardStudHiLo | Variant::Razz => 7,
+        }
+    }
+
+    pub fn max_players(&self) -> usize {
+        if self.is_stud() {
+            // Everyone needs six cards of their own; the last one can be shared.
+            52 / 6
+        } else if self.is_draw() {
+            // Whoever draws five must still find them in the stub and the muck.
+            (52 - self.hole_cards()) / self.hole_cards()
+        } else {
+            (52 - BOARD_AND_BURN_CARDS) / self.hole_cards()
+        }
+    }
+
+    pub fn is_stud(&self) -> bool {
+        matches!(
+            self,
+            Variant::SevenCardStud | Variant::SevenCardStudHiLo | Variant::Razz
+        )
+    }
+
+    pub fn is_draw(&self) -> bool {
+        self.draws() > 0
+    }
+
+    pub fn draws(&self) -> usize {
+        match self {
+            Variant::FiveCardDraw => 1,
+            Variant::DeuceToSevenTripleDraw => 3,
+            _ => 0,
+        }
+    }
+
+    pub fn best_hand(&self, hole_cards: &[Card], community_cards: &[Card]) -> BestHand {
+        match self {
+            Variant::Holdem
+            | Variant::SevenCardStud
+            | Variant::SevenCardStudHiLo
+            | Variant::Razz => evaluate_best_hand(hole_cards, community_cards),
+            Variant::Omaha
+            | Variant::FiveCardOmaha
+            | Variant::SixCardOmaha
+            | Variant::OmahaHiLo => evaluate_omaha_hand(hole_cards, community_cards),
+            Variant::FiveCardDraw | Variant::DeuceToSevenTripleDraw => BestHand::new(
+                hole_cards
+                    .try_into()
+                    .expect("A draw hand always holds five cards"),
+            ),
+        }
+    }
+
+    pub fn has_low(&self) -> bool {
+        matches!(self, Variant::OmahaHiLo | Variant::SevenCardStudHiLo)
+    }
+
+    pub fn best_low(&self, hole_cards: &[Card], community_cards: &[Card]) -> Option<LowHand> {
+        match self {
+            Variant::OmahaHiLo => evaluate_omaha_low(hole_cards, community_cards),
+            Variant::SevenCardStudHiLo => evaluate_best_low(hole_cards, community_cards),
+            _ => None,
+        }
+    }
+
+    pub fn lowball(&self) -> Option<Lowball> {
+        match self {
+            Variant::DeuceToSevenTripleDraw => Some(Lowball::DeuceToSeven),
+            Variant::Razz => Some(Lowball::AceToFive),
+            _ => None,
+        }
+    }
+
+    pub fn best_lowball(
+        &self,
+        hole_cards: &[Card],
+        community_cards: &[Card],
+    ) -> Option<LowballHand> {
+        self.lowball()
+            .map(|lowball| evaluate_best_lowball(lowball, hole_cards, community_cards))
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Variant::Holdem => "Hold'em",
+            Variant::Omaha => "Omaha",
+            Variant::FiveCardOmaha => "5-Card Omaha",
+            Variant::SixCardOmaha => "6-Card Omaha",
+            Variant::OmahaHiLo => "Omaha Hi-Lo",
+            Variant::SevenCardStud => "Seven Card Stud",
+            Variant::SevenCardStudHiLo => "Seven Card Stud Hi-Lo",
+            Variant::FiveCardDraw => "Five-Card Draw",
+            Variant::DeuceToSevenTripleDraw => "2-7 Triple Draw",
+            Variant::Razz => "Razz",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameError {
+    HandInProgress,
+    HandNotInProgress,
+    TooManyPlayers { variant: Variant, max: usize },
+    DrawInProgress,
+    NotDrawing,
+    InvalidDiscard(Card),
+    Betting(BettingError),
+    Chips(ChipError),
+    ChipsNotConserved { expected: u64, actual: u64 },
+    Deck(&'static str),
+    Table(TableError),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::HandInProgress => write!(f, "A hand is already in progress"),
+            GameError::HandNotInProgress => write!(f, "No hand is in progress"),
+            GameError::TooManyPlayers { variant, max } => {
+                write!(f, "{} can be dealt to at most {} players", variant, max)
+            }
+            GameError::DrawInProgress => write!(f, "Players are still drawing"),
+            GameError::NotDrawing => write!(f, "It is not time to draw"),
+            GameError::InvalidDiscard(card) => write!(f, "{} cannot be discarded", card),
+            GameError::Betting(error) => write!(f, "{}", error),
+            GameError::Chips(error) => write!(f, "{}", error),
+            GameError::ChipsNotConserved { expected, actual } => write!(
+                f,
+                "Expected {} chips in play but found {}",
+                expected, actual
+            ),
+            GameError::Deck(message) => write!(f, "{}", message),
+            GameError::Table(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<&'static str> for GameError {
+    fn from(message: &'static str) -> Self {
+        GameError::Deck(message)
+    }
+}
+
+impl From<BettingError> for GameError {
+    fn from(error: BettingError) -> Self {
+        GameError::Betting(error)
+    }
+}
+
+impl From<ChipError> for GameError {
+    fn from(error: ChipError) -> Self {
+        GameError::Chips(error)
+    }
+}
+
+impl From<TableError> for GameError {
+    fn from(error: TableError) -> Self {
+        GameError::Table(error)
+    }
+}
+
+pub struct Game<'a> {
+    table: Table<'a>,
+    deck: Deck,
+    seeds: ChaCha8Rng,
+    muck_rng: ChaCha8Rng,
+    hand_seed: Option<u64>,
+    community_cards: Vec<Card>,
+    muck: Vec<Card>,
+    draw_order: Vec<usize>,
+    draws_taken: usize,
+    pot: Pot,
+    round: Round,
+    street: Street,
+    variant: Variant,
+    betting_structure: BettingStructure,
+    blinds: BlindStructure,
+    chips_in_play: u64,
+    best_hands: Vec<Option<BestHand>>,
+    best_lows: Vec<Option<LowHand>>,
+    best_lowballs: Vec<Option<LowballHand>>,
+    odd_chip_rule: OddChipRule,
+    payouts: PayoutReport,
+}
+
+impl<'a> Game<'a> {
+    pub fn new(table: Table<'a>) -> Self {
+        let seats = table.seat_count();
+        Self {
+            table,
+            deck: Deck::new(),
+            seeds: ChaCha8Rng::from_entropy(),
+            muck_rng: ChaCha8Rng::from_entropy(),
+            hand_seed: None,
+            community_cards: Vec::new(),
+            muck: Vec::new(),
+            draw_order: Vec::new(),
+            draws_taken: 0,
+            pot: Pot::new(),
+            round: Round::new(seats, 0),
+            street: Street::Showdown,
+            variant: Variant::Holdem,
+            betting_structure: BettingStructure::NoLimit,
+            blinds: BlindStructure::default(),
+            chips_in_play: 0,
+            best_hands: vec![None; seats],
+            best_lows: vec![None; seats],
+            best_lowballs: vec![None; seats],
+            odd_chip_rule: OddChipRule::LeftOfButton,
+            payouts: PayoutReport::default(),
+        }
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seeds = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    pub fn hand_seed(&self) -> Option<u64> {
+        self.hand_seed
+    }
+
+    pub fn start_hand(&mut self) -> Result<(), GameError> {
+        let seed = self.seeds.next_u64();
+        self.start_hand_with_seed(seed)
+    }
+
+    pub fn start_hand_with_seed(&mut self, seed: u64) -> Result<(), GameError> {
+        if !self.is_hand_over() {
+            return Err(GameError::HandInProgress);
+        }
+        let players = self
+            .table
+            .players()
+            .filter(|(_, player)| !player.is_busted())
+            .count();
+        if players > self.variant.max_players() {
+            return Err(GameError::TooManyPlayers {
+                variant: self.variant,
+                max: self.variant.max_players(),
+            });
+        }
+
+        let positions = self.table.advance_button()?;
+
+        for seat in self.table.occupied_seats().collect::<Vec<_>>() {
+            let player = self.player_mut(seat);
+            player.clear_hand();
+            player.is_playing = !player.is_busted();
+        }
+
+        self.deck = Deck::new();
+        self.deck.shuffle_seeded(seed);
+        self.hand_seed = Some(seed);
+        // Reshuffles replay with the hand, on a stream apart from the deck's.
+        self.muck_rng = ChaCha8Rng::seed_from_u64(seed);
+        self.muck_rng.set_stream(1);
+        self.community_cards.clear();
+        self.muck.clear();
+        self.draw_order.clear();
+        self.draws_taken = 0;
+        self.pot = Pot::new();
+        self.best_hands.iter_mut().for_each(|hand| *hand = None);
+        self.best_lows.iter_mut().for_each(|low| *low = None);
+        self.best_lowballs.iter_mut().for_each(|low| *low = None);
+        self.payouts = PayoutReport::default();
+        self.chips_in_play = self.table.total_chips();
+
+        // Every seat dealt in is known to the pot, even before it puts chips in.
+        for seat in self.seats_in_hand() {
+            self.pot.add(seat, 0)?;
+        }
+
+        if self.variant.is_stud() {
+            self.start_stud()?;
+        } else {
+            self.start_with_blinds(&positions)?;
+        }
+
+        self.advance()?;
+        debug_assert_eq!(self.check_chips(), Ok(()));
+        Ok(())
+    }
+
+    fn start_with_blinds(&mut self, positions: &Positions) -> Result<(), GameError> {
+        self.street = if self.variant.is_draw() {
+            Street::BeforeDraw
+        } else {
+            Street::Preflop
+        };
+        let forced_bets = self.blinds.post(&mut self.table, positions)?;
+        let straddle = forced_bets
+            .iter()
+            .find(|bet| bet.kind == ForcedBetKind::Straddle);
+        let (last_blind, opening_bet) = match straddle {
+            Some(bet) => (bet.seat, self.blinds.straddle_amount()),
+            None => (positions.big_blind, self.blinds.big_blind),
+        };
+
+        self.round = self.new_round(opening_bet);
+        self.post_forced_bets(&forced_bets)?;
+
+        let seats = self.seats_in_hand();
+        let hands = self
+            .deck
+            .deal_to_players(seats.len(), self.variant.hole_cards())?;
+        for (seat, hand) in seats.into_iter().zip(hands) {
+            for card in hand {
+                self.player_mut(seat).receive_card(card);
+            }
+        }
+
+        self.round.start(last_blind);
+        Ok(())
+    }
+
+    fn start_stud(&mut self) -> Result<(), GameError> {
+        self.street = Street::Third;
+        let seats = self.seats_in_hand();
+        let hands = self.deck.deal_to_players(seats.len(), 3)?;
+        for (seat, hand) in seats.into_iter().zip(hands) {
+            let player = self.player_mut(seat);
+            player.receive_card(hand[0]);
+            player.receive_card(hand[1]);
+            player.receive_up_card(hand[2]);
+        }
+
+        let bring_in = self.bring_in_seat();
+        let forced_bets = self.blinds.post_stud(&mut self.table, bring_in)?;
+        self.round = self.new_round(self.blinds.big_blind);
+        self.post_forced_bets(&forced_bets)?;
+        self.round.open_with_bring_in(
+            self.betting_structure
+                .opening_raise(self.blinds.big_blind, false),
+        );
+        self.round.start(bring_in);
+        Ok(())
+    }
+
+    fn post_forced_bets(&mut self, bets: &[ForcedBet]) -> Result<(), GameError> {
+        for bet in bets {
+            self.pot.add(bet.seat, bet.amount)?;
+            if bet.kind.is_live() {
+                let all_in = self.player_mut(bet.seat).chips == 0;
+                self.round.post(bet.seat, bet.amount, all_in);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn act(&mut self, action: Action) -> Result<(), GameError> {
+        if self.is_drawing() {
+            return Err(GameError::DrawInProgress);
+        }
+        let (Some(seat), Some(context)) = (self.round.to_act(), self.betting_context()) else {
+            return Err(GameError::HandNotInProgress);
+        };
+
+        let action = self.betting_structure.normalize(action, &context)?;
+        match action {
+            Action::Fold => {
+                self.player_mut(seat).fold();
+                self.pot.fold(seat);
+            }
+            Action::Check => {}
+            Action::Call(amount) => {
+                let paid = self.player_mut(seat).call(amount)?;
+                self.pot.add(seat, paid)?;
+            }
+            Action::Raise(amount) => {
+                let paid = self.player_mut(seat).bet(context.to_call + amount)?;
+                self.pot.add(seat, paid)?;
+            }
+        }
+
+        let all_in = self.player_mut(seat).chips == 0;
+        self.round.act(action, all_in);
+        self.advance()?;
+        debug_assert_eq!(self.check_chips(), Ok(()));
+        Ok(())
+    }
+
+    pub fn discard(&mut self, cards: &[Card]) -> Result<Vec<Card>, GameError> {
+        let Some(&seat) = self.draw_order.first().filter(|_| self.is_drawing()) else {
+            return Err(GameError::NotDrawing);
+        };
+
+        let hand = &self
+            .table
+            .player(seat)
+            .expect("Seats in a hand are occupied")
+            .hand();
+        let mut discards = CardSet::new();
+        for &card in cards {
+            if !hand.contains(&card) || !discards.insert(card) {
+                return Err(GameError::InvalidDiscard(card));
+            }
+        }
+
+        // A player's own discards never come back to them in a reshuffle.
+        let replacements = self.draw_cards(cards.len());
+        let player = self.player_mut(seat);
+        for &card in cards {
+            player.discard(card);
+        }
+        for &card in &replacements {
+            player.receive_card(card);
+        }
+        self.muck.extend_from_slice(cards);
+
+        self.draw_order.remove(0);
+        if self.draw_order.is_empty() {
+            self.next_street()?;
+            self.advance()?;
+        }
+        debug_assert_eq!(self.check_chips(), Ok(()));
+        Ok(replacements)
+    }
+
+    pub fn check_chips(&self) -> Result<(), GameError> {
+        if self.is_hand_over() {
+            return Ok(());
+        }
+
+        let actual = self.table.total_chips() + u64::from(self.pot.total());
+        if actual != self.chips_in_play {
+            return Err(GameError::ChipsNotConserved {
+                expected: self.chips_in_play,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn set_blinds(&mut self, blinds: BlindStructure) {
+        self.blinds = blinds;
+    }
+
+    pub fn blinds(&self) -> BlindStructure {
+        self.blinds
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) -> Result<(), GameError> {
+        if !self.is_hand_over() {
+            return Err(GameError::HandInProgress);
+        }
+        self.variant = variant;
+        Ok(())
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn set_betting_structure(&mut self, structure: BettingStructure) {
+        self.betting_structure = structure;
+    }
+
+    pub fn betting_structure(&self) -> BettingStructure {
+        self.betting_structure
+    }
+
+    pub fn raise_limits(&self) -> Option<(u32, u32)> {
+        self.betting_context()
+            .and_then(|context| self.betting_structure.raise_limits(&context))
+    }
+
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    pub fn is_hand_over(&self) -> bool {
+        self.street == Street::Showdown
+    }
+
+    pub fn current_player(&self) -> Option<usize> {
+        if self.is_hand_over() {
+            None
+        } else if self.is_drawing() {
+            self.draw_order.first().copied()
+        } else {
+            self.round.to_act()
+        }
+    }
+
+    pub fn is_drawing(&self) -> bool {
+        self.street == Street::Draw
+    }
+
+    pub fn to_call(&self) -> u32 {
+        self.current_player()
+            .map_or(0, |seat| self.round.to_call(seat))
+    }
+
+    pub fn positions(&self) -> Option<Positions> {
+        self.table.positions()
+    }
+
+    pub fn table(&self) -> &Table<'a> {
+        &self.table
+    }
+
+    pub fn table_mut(&mut self) -> Result<&mut Table<'a>, GameError> {
+        if !self.is_hand_over() {
+            return Err(GameError::HandInProgress);
+        }
+        Ok(&mut self.table)
+    }
+
+    pub fn into_table(self) -> Table<'a> {
+        self.table
+    }
+
+    pub fn player(&self, seat: usize) -> Option<&Player<'a>> {
+        self.table.player(seat)
+    }
+
+    pub fn community_cards(&self) -> &[Card] {
+        &self.community_cards
+    }
+
+    pub fn pot_total(&self) -> u32 {
+        self.pot.total()
+    }
+
+    pub fn best_hand(&self, seat: usize) -> Option<&BestHand> {
+        self.best_hands.get(seat).and_then(|hand| hand.as_ref())
+    }
+
+    pub fn best_low(&self, seat: usize) -> Option<&LowHand> {
+        self.best_lows.get(seat).and_then(|low| low.as_ref())
+    }
+
+    pub fn best_lowball(&self, seat: usize) -> Option<&LowballHand> {
+        self.best_lowballs.get(seat).and_then(|low| low.as_ref())
+    }
+
+    pub fn winners(&self) -> Vec<usize> {
+        self.payouts.winners()
+    }
+
+    pub fn payouts(&self) -> &PayoutReport {
+        &self.payouts
+    }
+
+    pub fn set_odd_chip_rule(&mut self, rule: OddChipRule) {
+        self.odd_chip_rule = rule;
+    }
+
+    fn player_mut(&mut self, seat: usize) -> &mut Player<'a> {
+        self.table
+            .player_mut(seat)
+            .expect("Seats in a hand are occupied")
+    }
+
+    fn betting_context(&self) -> Option<BettingContext> {
+        if self.is_drawing() {
+            return None;
+        }
+        let seat = self.current_player()?;
+        Some(BettingContext {
+            to_call: self.round.to_call(seat),
+            chips: self.table.player(seat).map_or(0, |player| player.chips),
+            pot: self.pot.total(),
+            min_raise: self.round.last_raise(),
+            raises: self.round.raises(),
+            big_bet_street: self.is_big_bet_street(),
+            can_raise: self.round.can_raise(seat),
+            bring_in: self.round.bring_in(),
+        })
+    }
+
+    fn is_big_bet_street(&self) -> bool {
+        match self.street {
+            Street::Turn | Street::River | Street::Fifth | Street::Sixth | Street::Seventh => true,
+            // Triple draw bets the big bet after its second and third draws.
+            Street::AfterDraw => self.draws_taken > self.variant.draws() / 2,
+            _ => false,
+        }
+    }
+
+    fn new_round(&self, opening_bet: u32) -> Round {
+        let min_raise = self
+            .betting_structure
+            .opening_raise(opening_bet, self.is_big_bet_street());
+        let mut round = Round::new(self.table.seat_count(), min_raise);
+        for (seat, player) in self.table.players() {
+            if player.is_playing {
+                round.join(seat, player.chips == 0);
+            }
+        }
+        round
+    }
+
+    fn in_hand(&self, seat: usize) -> bool {
+        self.table
+            .player(seat)
+            .is_some_and(|player| player.is_playing)
+    }
+
+    fn seats_in_hand(&self) -> Vec<usize> {
+        (0..self.table.seat_count())
+            .filter(|&seat| self.in_hand(seat))
+            .collect()
+    }
+
+    // The lowest up card brings it in; equal ranks go to the lower suit,
+    // clubs first and spades last. Razz turns this around: the highest card,
+    // with the ace low, brings it in and spades go first.
+    fn bring_in_seat(&self) -> usize {
+        let up_card = |seat: usize| {
+            let card = self.up_cards(seat)[0];
+            let rank = match self.variant.lowball() {
+                Some(_) => card.get_rank().ace_low_value(),
+                None => card.get_rank() as u8,
+            };
+            (rank, card.get_suit().tie_break_rank())
+        };
+
+        let seats = self.seats_in_hand().into_iter();
+        match self.variant.lowball() {
+            Some(_) => seats.max_by_key(|&seat| up_card(seat)),
+            None => seats.min_by_key(|&seat| up_card(seat)),
+        }
+        .expect("A stud hand has players")
+    }
+
+    // From fourth street on, the best hand showing acts first; ties go to
+    // the lowest seat.
+    fn best_showing_seat(&self) -> usize {
+        let seats = self.seats_in_hand().into_iter();
+        match self.variant.lowball() {
+            Some(_) => seats.min_by_key(|&seat| showing_low(&self.up_cards(seat))),
+            None => seats.min_by_key(|&seat| Reverse(showing_strength(&self.up_cards(seat)))),
+        }
+        .expect("A stud hand has players")
+    }
+
+    fn up_cards(&self, seat: usize) -> Vec<Card> {
+        self.table
+            .player(seat)
+            .expect("Seats in a hand are occupied")
+            .up_cards()
+    }
+
+    fn button(&self) -> usize {
+        self.table.button().unwrap_or(0)
+    }
+
+    fn advance(&mut self) -> Result<(), GameError> {
+        if self.round.players_left() == 1 {
+            self.street = Street::Showdown;
+            return self.award_pots();
+        }
+
+        while self.round.is_over() && !self.is_drawing() {
+            self.next_street()?;
+            if self.street == Street::Showdown {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn next_street(&mut self) -> Result<(), GameError> {
+        self.street = match self.street {
+            Street::Preflop => {
+                self.deal_community(3);
+                Street::Flop
+            }
+            Street::Flop => {
+                self.deal_community(1);
+                Street::Turn
+            }
+            Street::Turn => {
+                self.deal_community(1);
+                Street::River
+            }
+            Street::Third => {
+                self.deal_stud_cards(true);
+                Street::Fourth
+            }
+            Street::Fourth => {
+                self.deal_stud_cards(true);
+                Street::Fifth
+            }
+            Street::Fifth => {
+                self.deal_stud_cards(true);
+                Street::Sixth
+            }
+            Street::Sixth => {
+                self.deal_stud_cards(false);
+                Street::Seventh
+            }
+            Street::BeforeDraw | Street::AfterDraw if self.draws_taken < self.variant.draws() => {
+                let seat_count = self.table.seat_count();
+                let button = self.button();
+                self.draw_order = (1..=seat_count)
+                    .map(|offset| (button + offset) % seat_count)
+                    .filter(|&seat| self.in_hand(seat))
+                    .collect();
+                Street::Draw
+            }
+            Street::Draw => {
+                self.draws_taken += 1;
+                Street::AfterDraw
+            }
+            Street::River
+            | Street::Seventh
+            | Street::BeforeDraw
+            | Street::AfterDraw
+            | Street::Showdown => {
+                self.showdown()?;
+                Street::Showdown
+            }
+        };
+
+        self.round = self.new_round(self.blinds.big_blind);
+        if self.variant.is_stud() {
+            let first = self.best_showing_seat();
+            let seat_count = self.table.seat_count();
+            self.round.start((first + seat_count - 1) % seat_count);
+        } else {
+            self.round.start(self.button());
+        }
+        Ok(())
+    }
+
+    fn deal_community(&mut self, count: usize) {
+        self.deck.draw();
+        let cards = self
+            .deck
+            .deal(count)
+            .expect("A 52-card deck always covers the board");
+        self.community_cards.extend(cards);
+    }
+
+    // Stud deals without burn cards. When the deck cannot give everyone a
+    // card, a single community card is dealt face up for all to share.
+    fn deal_stud_cards(&mut self, face_up: bool) {
+        let seats = self.seats_in_hand();
+        let Ok(cards) = self.deck.deal_to_players(seats.len(), 1) else {
+            let card = self
+                .deck
+                .draw()
+                .expect("Eight stud players never empty the deck");
+            self.community_cards.push(card);
+            return;
+        };
+
+        for (seat, card) in seats.into_iter().zip(cards) {
+            let player = self.player_mut(seat);
+            if face_up {
+                player.receive_up_card(card[0]);
+            } else {
+                player.receive_card(card[0]);
+            }
+        }
+    }
+
+    // When the stub runs short the muck is shuffled in underneath it.
+    fn draw_cards(&mut self, count: usize) -> Vec<Card> {
+        if self.deck.remaining_cards() < count {
+            let mut muck = std::mem::take(&mut self.muck);
+            muck.shuffle(&mut self.muck_rng);
+            self.deck.add_to_bottom(muck);
+        }
+        self.deck
+            .deal(count)
+            .expect("The stub and the muck always cover a draw")
+    }
+
+    fn showdown(&mut self) -> Result<(), GameError> {
+        for seat in 0..self.table.seat_count() {
+            if self.in_hand(seat) {
+                let player = self
+                    .table
+                    .player(seat)
+                    .expect("Seats in a hand are occupied");
+                if self.variant.lowball().is_some() {
+                    self.best_lowballs[seat] = self
+                        .variant
+                        .best_lowball(player.hand(), &self.community_cards);
+                } else {
+                    self.best_hands[seat] =
+                        Some(self.variant.best_hand(player.hand(), &self.community_cards));
+                    self.best_lows[seat] =
+                        self.variant.best_low(player.hand(), &self.community_cards);
+                }
+            }
+        }
+
+        self.award_pots()
+    }
+
+    fn award_pots(&mut self) -> Result<(), GameError> {
+        let odd_chip_order =
+            self.odd_chip_rule
+                .seat_order(self.button(), self.table.seat_count(), |seat| {
+                    self.table
+                        .player(seat)
+                        .and_then(|player| {
+                            player.hand().iter().max_by_key(|card| {
+                                (card.get_rank(), card.get_suit().tie_break_rank())
+                            })
+                        })
+                        .copied()
+                });
+
+        let high = |seat: usize| self.best_hands[seat].clone();
+        self.payouts = if self.variant.lowball().is_some() {
+            self.pot.pay_out(
+                |seat| self.best_lowballs[seat].map(Reverse),
+                &odd_chip_order,
+            )
+        } else if self.variant.has_low() {
+            self.pot.pay_out_split(
+                high,
+                |seat| self.best_lows[seat].map(Reverse),
+                &odd_chip_order,
+            )
+        } else {
+            self.pot.pay_out(high, &odd_chip_order)
+        };
+        self.payouts.apply(&mut self.table)?;
+        self.pot.clear();
+        Ok(())
+    }
+}
+
+// Only pairs, trips and quads count among up cards; straights and flushes
+// showing do not.
+fn showing_strength(cards: &[Card]) -> Vec<(usize, Rank)> {
+    let mut groups: Vec<(usize, Rank)> = cards
+        .iter()
+        .counts_by(|card| card.get_rank())
+        .into_iter()
+        .map(|(rank, count)| (count, rank))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    groups
+}
+
+// Razz up cards are read as a low: fewer pairs first, then the lowest
+// cards with the ace low.
+fn showing_low(cards: &[Card]) -> Vec<(usize, u8)> {
+    let mut groups: Vec<(usize, u8)> = cards
+        .iter()
+        .counts_by(|card| card.get_rank().ace_low_value())
+        .into_iter()
+        .map(|(value, count)| (count, value))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn new_game(stacks: &[u32]) -> Game<'static> {
+        let names = [
+            "Dusan", "Ana", "Marko", "Jelena", "Nikola", "Ivana", "Stefan", "Milica",
+        ];
+        let players = stacks
+            .iter()
+            .zip(names)
+            .map(|(&chips, name)| Player::new(name, chips))
+            .collect();
+        let mut table = Table::with_players(players).unwrap();
+        table.set_button(0).unwrap();
+        Game::new(table)
+    }
+
+    fn total_chips(game: &Game) -> u32 {
+        game.table()
+            .players()
+            .map(|(_, player)| player.chips)
+            .sum::<u32>()
+            + game.pot_total()
+    }
+
+    #[test]
+    fn test_needs_two_players() {
+        let mut game = new_game(&[100, 0]);
+        assert_eq!(
+            game.start_hand(),
+            Err(GameError::Table(TableError::NotEnoughPlayers))
+        );
+    }
+
+    #[test]
+    fn test_start_hand_posts_blinds_and_deals() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+
+        assert_eq!(game.street(), Street::Preflop);
+        assert_eq!(game.pot_total(), 75);
+        assert_eq!(
+            game.positions(),
+            Some(Positions {
+                button: 0,
+                small_blind: Some(1),
+                big_blind: 2
+            })
+        );
+        assert_eq!(game.player(1).unwrap().chips, 975);
+        assert_eq!(game.player(2).unwrap().chips, 950);
+        assert_eq!(game.current_player(), Some(0));
+        assert_eq!(game.to_call(), 50);
+        assert!(game
+            .table()
+            .players()
+            .all(|(_, player)| player.hand().len() == 2));
+        assert_eq!(game.start_hand(), Err(GameError::HandInProgress));
+    }
+
+    #[test]
+    fn test_heads_up_dealer_posts_small_blind() {
+        let mut game = new_game(&[1000, 1000]);
+        game.start_hand().unwrap();
+
+        assert_eq!(game.player(0).unwrap().chips, 975);
+        assert_eq!(game.player(1).unwrap().chips, 950);
+        assert_eq!(game.current_player(), Some(0));
+    }
+
+    #[test]
+    fn test_rejects_illegal_actions() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+
+        assert_eq!(
+            game.act(Action::Check),
+            Err(GameError::Betting(BettingError::CannotCheck))
+        );
+        assert_eq!(
+            game.act(Action::Call(20)),
+            Err(GameError::Betting(BettingError::WrongCallAmount {
+                expected: 50
+            }))
+        );
+        assert_eq!(
+            game.act(Action::Raise(0)),
+            Err(GameError::Betting(BettingError::InvalidRaise))
+        );
+        assert_eq!(
+            game.act(Action::Raise(5000)),
+            Err(GameError::Betting(BettingError::InsufficientChips))
+        );
+
+        game.act(Action::Call(50)).unwrap();
+        game.act(Action::Call(25)).unwrap();
+        game.act(Action::Check).unwrap();
+        assert_eq!(game.street(), Street::Flop);
+        assert_eq!(
+            game.act(Action::Call(0)),
+            Err(GameError::Betting(BettingError::NothingToCall))
+        );
+    }
+
+    #[test]
+    fn test_raise_must_match_the_last_raise() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+
+        assert_eq!(
+            game.act(Action::Raise(30)),
+            Err(GameError::Betting(BettingError::RaiseTooSmall { min: 50 }))
+        );
+        game.act(Action::Raise(150)).unwrap();
+        assert_eq!(game.to_call(), 175);
+        assert_eq!(game.raise_limits(), Some((150, 800)));
+        assert_eq!(
+            game.act(Action::Raise(100)),
+            Err(GameError::Betting(BettingError::RaiseTooSmall { min: 150 }))
+        );
+    }
+
+    #[test]
+    fn test_incomplete_all_in_does_not_reopen_action() {
+        let mut game = new_game(&[1000, 1000, 1000, 260]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Call(50)).unwrap();
+        game.act(Action::Raise(150)).unwrap();
+        game.act(Action::Call(175)).unwrap();
+        game.act(Action::Call(150)).unwrap();
+
+        assert_eq!(game.current_player(), Some(3));
+        game.act(Action::Raise(60)).unwrap();
+
+        assert_eq!(game.current_player(), Some(0));
+        assert_eq!(game.to_call(), 60);
+        assert_eq!(game.raise_limits(), None);
+        assert_eq!(
+            game.act(Action::Raise(200)),
+            Err(GameError::Betting(BettingError::ActionNotReopened))
+        );
+        game.act(Action::Call(60)).unwrap();
+    }
+
+    #[test]
+    fn test_short_all_ins_that_add_up_to_a_full_raise_reopen_action() {
+        let mut game = new_game(&[360, 1000, 1000, 1000, 260]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Raise(150)).unwrap();
+        game.act(Action::Raise(60)).unwrap();
+        game.act(Action::Raise(100)).unwrap();
+        game.act(Action::Call(335)).unwrap();
+        game.act(Action::Call(310)).unwrap();
+
+        assert_eq!(game.current_player(), Some(3));
+        assert_eq!(game.to_call(), 160);
+        assert_eq!(game.raise_limits(), Some((150, 640)));
+        game.act(Action::Raise(150)).unwrap();
+        assert_eq!(game.current_player(), Some(1));
+    }
+
+    #[test]
+    fn test_fixed_limit_caps_raises() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.set_betting_structure(BettingStructure::FixedLimit {
+            small_bet: 50,
+            big_bet: 100,
+            max_raises: 3,
+        });
+        game.start_hand().unwrap();
+
+        game.act(Action::Raise(500)).unwrap();
+        assert_eq!(game.to_call(), 75);
+        game.act(Action::Raise(50)).unwrap();
+        game.act(Action::Raise(50)).unwrap();
+        assert_eq!(game.raise_limits(), None);
+        assert_eq!(
+            game.act(Action::Raise(50)),
+            Err(GameError::Betting(BettingError::RaiseCapReached))
+        );
+        game.act(Action::Call(100)).unwrap();
+        game.act(Action::Call(50)).unwrap();
+
+        assert_eq!(game.street(), Street::Flop);
+        assert_eq!(game.pot_total(), 600);
+        assert_eq!(game.raise_limits(), Some((50, 50)));
+    }
+
+    #[test]
+    fn test_big_blind_gets_option() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Call(50)).unwrap();
+        game.act(Action::Call(25)).unwrap();
+        assert_eq!(game.street(), Street::Preflop);
+        assert_eq!(game.current_player(), Some(2));
+
+        game.act(Action::Raise(100)).unwrap();
+        assert_eq!(game.street(), Street::Preflop);
+        assert_eq!(game.current_player(), Some(0));
+        assert_eq!(game.to_call(), 100);
+    }
+
+    #[test]
+    fn test_everyone_folds_to_big_blind() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Fold).unwrap();
+        game.act(Action::Fold).unwrap();
+
+        assert!(game.is_hand_over());
+        assert_eq!(game.winners(), vec![2]);
+        assert_eq!(game.payouts().amount(2), 75);
+        assert_eq!(game.player(1).unwrap().chips, 975);
+        assert_eq!(game.player(2).unwrap().chips, 1025);
+        assert_eq!(game.act(Action::Check), Err(GameError::HandNotInProgress));
+    }
+
+    #[test]
+    fn test_button_moves_between_hands() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+        game.act(Action::Fold).unwrap();
+        game.act(Action::Fold).unwrap();
+
+        game.table_mut()
+            .unwrap()
+            .sit_anywhere(Player::new("Jelena", 1000))
+            .unwrap_err();
+        game.start_hand().unwrap();
+
+        assert_eq!(game.positions().unwrap().button, 1);
+        assert_eq!(game.current_player(), Some(1));
+        assert_eq!(total_chips(&game), 3000);
+    }
+
+    #[test]
+    fn test_plays_through_to_showdown() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Call(50)).unwrap();
+        game.act(Action::Call(25)).unwrap();
+        game.act(Action::Check).unwrap();
+
+        for street in [Street::Flop, Street::Turn, Street::River] {
+            assert_eq!(game.street(), street);
+            assert_eq!(game.current_player(), Some(1));
+            for _ in 0..3 {
+                game.act(Action::Check).unwrap();
+            }
+        }
+
+        assert!(game.is_hand_over());
+        assert_eq!(game.community_cards().len(), 5);
+        assert!(!game.winners().is_empty());
+        assert!(game.best_hand(0).is_some());
+        assert_eq!(game.pot_total(), 0);
+        assert_eq!(total_chips(&game), 3000);
+    }
+
+    #[test]
+    fn test_all_in_runs_out_the_board() {
+        let mut game = new_game(&[1000, 1000]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Raise(950)).unwrap();
+        game.act(Action::Call(950)).unwrap();
+
+        assert!(game.is_hand_over());
+        assert_eq!(game.community_cards().len(), 5);
+        assert_eq!(total_chips(&game), 2000);
+    }
+
+    #[test]
+    fn test_short_all_in_only_wins_main_pot() {
+        let mut game = new_game(&[1000, 100, 1000]);
+        game.start_hand().unwrap();
+
+        game.act(Action::Raise(950)).unwrap();
+        game.act(Action::Call(75)).unwrap();
+        game.act(Action::Call(950)).unwrap();
+
+        assert!(game.is_hand_over());
+        assert_eq!(game.pot_total(), 0);
+        assert!(game.player(1).unwrap().chips <= 300);
+        assert_eq!(total_chips(&game), 2100);
+        assert!(game.winners().iter().any(|&seat| seat != 1));
+    }
+
+    #[test]
+    fn test_short_big_blind_posts_all_in() {
+        let mut game = new_game(&[1000, 1000, 30]);
+        game.start_hand().unwrap();
+
+        assert_eq!(game.pot_total(), 55);
+        assert_eq!(game.player(2).unwrap().chips, 0);
+        assert_eq!(game.to_call(), 30);
+        game.act(Action::Call(30)).unwrap();
+        game.act(Action::Call(5)).unwrap();
+
+        assert_eq!(game.street(), Street::Flop);
+        assert_eq!(game.current_player(), Some(1));
+        assert_eq!(total_chips(&game), 2030);
+    }
+
+    #[test]
+    fn test_straddle_acts_last_preflop() {
+        let mut game = new_game(&[1000, 1000, 1000, 1000]);
+        game.set_blinds(BlindStructure {
+            ante: 5,
+            straddle: true,
+            ..BlindStructure::default()
+        });
+        game.start_hand().unwrap();
+
+        assert_eq!(game.pot_total(), 195);
+        assert_eq!(game.current_player(), Some(0));
+        assert_eq!(game.to_call(), 100);
+        assert_eq!(game.raise_limits(), Some((100, 895)));
+
+        game.act(Action::Call(100)).unwrap();
+        game.act(Action::Call(75)).unwrap();
+        game.act(Action::Call(50)).unwrap();
+        assert_eq!(game.current_player(), Some(3));
+        game.act(Action::Check).unwrap();
+        assert_eq!(game.street(), Street::Flop);
+        assert_eq!(game.pot_total(), 420);
+    }
+
+    #[test]
+    fn test_chip_check_catches_missing_chips() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.start_hand().unwrap();
+        game.act(Action::Raise(100)).unwrap();
+        assert_eq!(game.check_chips(), Ok(()));
+
+        game.table.player_mut(1).unwrap().chips -= 10;
+        assert_eq!(
+            game.check_chips(),
+            Err(GameError::ChipsNotConserved {
+                expected: 3000,
+                actual: 2990
+            })
+        );
+    }
+
+    #[test]
+    fn test_omaha_deals_four_cards_and_plays_two() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.set_variant(Variant::Omaha).unwrap();
+        game.set_betting_structure(BettingStructure::PotLimit);
+        game.start_hand().unwrap();
+
+        assert!(game
+            .table()
+            .players()
+            .all(|(_, player)| player.hand().len() == 4));
+        assert_eq!(
+            game.set_variant(Variant::Holdem),
+            Err(GameError::HandInProgress)
+        );
+
+        game.act(Action::Call(50)).unwrap();
+        game.act(Action::Call(25)).unwrap();
+        game.act(Action::Check).unwrap();
+        while !game.is_hand_over() {
+            game.act(Action::Check).unwrap();
+        }
+
+        for seat in 0..3 {
+            let hand = &game.player(seat).unwrap().hand();
+            let best = game.best_hand(seat).unwrap();
+            assert_eq!(best, &evaluate_omaha_hand(hand, game.community_cards()));
+            assert_eq!(
+                best.cards.iter().filter(|card| hand.contains(card)).count(),
+                2
+            );
+        }
+        assert_eq!(total_chips(&game), 3000);
+    }
+
+    #[test]
+    fn test_six_card_omaha_limits_the_table() {
+        let mut game = new_game(&[1000; 8]);
+        game.set_variant(Variant::SixCardOmaha).unwrap();
+        assert_eq!(
+            game.start_hand(),
+            Err(GameError::TooManyPlayers {
+                variant: Variant::SixCardOmaha,
+                max: 7
+            })
+        );
+        assert!(game.is_hand_over());
+
+        game.table_mut().unwrap().player_mut(7).unwrap().chips = 0;
+        game.start_hand().unwrap();
+        assert_eq!(game.player(0).unwrap().hand().len(), 6);
+    }
+
+    #[test]
+    fn test_omaha_hi_lo_splits_only_with_a_qualifying_low() {
+        let mut split = false;
+        let mut scooped = false;
+
+        for seed in 0..20 {
+            let mut game = new_game(&[1000, 1000, 1000]);
+            game.set_variant(Variant::OmahaHiLo).unwrap();
+            game.start_hand_with_seed(seed).unwrap();
+            game.act(Action::Call(50)).unwrap();
+            game.act(Action::Call(25)).unwrap();
+            while !game.is_hand_over() {
+                game.act(Action::Check).unwrap();
+            }
+
+            let awards = &game.payouts().awards;
+            let best_low = (0..3).filter_map(|seat| game.best_low(seat)).min();
+            match best_low {
+                Some(low) => {
+                    split = true;
+                    assert_eq!(awards.len(), 2);
+                    assert_eq!(awards[1].amount, 75);
+                    for &seat in &awards[1].winners {
+                        assert_eq!(game.best_low(seat), Some(low));
+                    }
+                }
+                None => {
+                    scooped = true;
+                    assert_eq!(awards.len(), 1);
+                    assert_eq!(awards[0].amount, 150);
+                }
+            }
+            assert_eq!(total_chips(&game), 3000);
+        }
+
+        assert!(split && scooped);
+    }
+
+    fn call_or_check(game: &mut Game) {
+        let action = match game.to_call() {
+            0 => Action::Check,
+            amount => Action::Call(amount),
+        };
+        game.act(action).unwrap();
+    }
+
+    #[test]
+    fn test_stud_antes_and_lowest_up_card_brings_in() {
+        let mut game = new_game(&[1000, 1000, 1000, 1000]);
+        game.set_variant(Variant::SevenCardStud).unwrap();
+        game.set_blinds(BlindStructure {
+            ante: 5,
+            ..BlindStructure::default()
+        });
+        game.start_hand_with_seed(3).unwrap();
+
+        assert_eq!(game.street(), Street::Third);
+        assert_eq!(game.pot_total(), 45);
+        let mut lowest = None;
+        for (seat, player) in game.table().players() {
+            assert_eq!(player.hand().len(), 3);
+            assert_eq!(player.up_cards(), vec![player.hand()[2]]);
+            let card = player.hand()[2];
+            let key = (card.get_rank(), card.get_suit().tie_break_rank());
+            if lowest.is_none_or(|(_, lowest)| key < lowest) {
+                lowest = Some((seat, key));
+            }
+        }
+
+        let (bring_in, _) = lowest.unwrap();
+        assert_eq!(game.player(bring_in).unwrap().chips, 970);
+        assert_eq!(game.current_player(), Some((bring_in + 1) % 4));
+        assert_eq!(game.to_call(), 25);
+    }
+
+    #[test]
+    fn test_stud_bring_in_can_be_completed_and_then_raised() {
+        for structure in [
+            BettingStructure::NoLimit,
+            BettingStructure::FixedLimit {
+                small_bet: 50,
+                big_bet: 100,
+                max_raises: 3,
+            },
+        ] {
+            let mut game = new_game(&[1000, 1000, 1000, 1000, 1000]);
+            game.set_variant(Variant::SevenCardStud).unwrap();
+            game.set_betting_structure(structure);
+            game.start_hand_with_seed(3).unwrap();
+            assert_eq!(game.to_call(), 25);
+            assert_eq!(game.raise_limits().map(|(min, _)| min), Some(25));
+
+            let completer = game.current_player().unwrap();
+            game.act(Action::Raise(25)).unwrap();
+            assert_eq!(game.player(completer).unwrap().chips, 950);
+
+            assert_eq!(game.to_call(), 50);
+            if structure == BettingStructure::NoLimit {
+                assert_eq!(
+                    game.act(Action::Raise(25)),
+                    Err(GameError::Betting(BettingError::RaiseTooSmall { min: 50 }))
+                );
+            }
+            let raiser = game.current_player().unwrap();
+            game.act(Action::Raise(50)).unwrap();
+            assert_eq!(game.player(raiser).unwrap().chips, 900);
+
+            // The completion is the bet, so three raises are still allowed.
+            if structure != BettingStructure::NoLimit {
+                game.act(Action::Raise(50)).unwrap();
+                game.act(Action::Raise(50)).unwrap();
+                assert_eq!(game.to_call(), 175);
+                assert_eq!(game.raise_limits(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stud_plays_from_third_to_seventh_street() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.set_variant(Variant::SevenCardStud).unwrap();
+        game.start_hand_with_seed(11).unwrap();
+
+        let mut streets = vec![game.street()];
+        while !game.is_hand_over() {
+            if game.street() != *streets.last().unwrap() {
+                streets.push(game.street());
+                let best_showing = (0..3)
+                    .max_by_key(|&seat| {
+                        (
+                            showing_strength(&game.player(seat).unwrap().up_cards()),
+                            Reverse(seat),
+                        )
+                    })
+                    .unwrap();
+                assert_eq!(game.current_player(), Some(best_showing));
+            }
+            call_or_check(&mut game);
+        }
+
+        assert_eq!(
+            streets,
+            vec![
+                Street::Third,
+                Street::Fourth,
+                Street::Fifth,
+                Street::Sixth,
+                Street::Seventh
+            ]
+        );
+        assert!(game.community_cards().is_empty());
+        for (seat, player) in game.table().players() {
+            assert_eq!(player.hand().len(), 7);
+            assert_eq!(player.up_cards(), player.hand()[2..6]);
+            assert!(game.best_hand(seat).is_some());
+        }
+        assert_eq!(total_chips(&game), 3000);
+    }
+
+    #[test]
+    fn test_stud_shares_a_community_card_when_the_deck_runs_out() {
+        let mut game = new_game(&[1000; 8]);
+        game.set_variant(Variant::SevenCardStud).unwrap();
+        game.start_hand().unwrap();
+        while !game.is_hand_over() {
+            call_or_check(&mut game);
+        }
+
+        assert_eq!(game.community_cards().len(), 1);
+        assert!(game
+            .table()
+            .players()
+            .all(|(_, player)| player.hand().len() == 6));
+        assert_eq!(total_chips(&game), 8000);
+        assert_eq!(Variant::SevenCardStud.max_players(), 8);
+    }
+
+    #[test]
+    fn test_showing_strength_counts_pairs_only() {
+        let cards = |text| parse_cards(text).unwrap();
+        assert!(showing_strength(&cards("2c 2d")) > showing_strength(&cards("Ac Kc")));
+        assert!(showing_strength(&cards("3c 3d 3h")) > showing_strength(&cards("Ac Ad Kc Kd")));
+        assert!(showing_strength(&cards("9c 9d Ah")) > showing_strength(&cards("9h 9s Kh")));
+        assert!(showing_strength(&cards("5h 6h 7h 8h")) < showing_strength(&cards("2c 2d")));
+    }
+
+    #[test]
+    fn test_five_card_draw_discards_and_redraws() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.set_variant(Variant::FiveCardDraw).unwrap();
+        game.start_hand().unwrap();
+
+        assert_eq!(game.street(), Street::BeforeDraw);
+        assert_eq!(game.discard(&[]), Err(GameError::NotDrawing));
+        while game.street() == Street::BeforeDraw {
+            call_or_check(&mut game);
+        }
+
+        assert!(game.is_drawing());
+        assert_eq!(game.current_player(), Some(1));
+        assert_eq!(game.raise_limits(), None);
+        assert_eq!(game.act(Action::Check), Err(GameError::DrawInProgress));
+
+        let hand = game.player(1).unwrap().hand().to_vec();
+        let stranger = Deck::new()
+            .deal(52)
+            .unwrap()
+            .into_iter()
+            .find(|card| !hand.contains(card))
+            .unwrap();
+        assert_eq!(
+            game.discard(&[stranger]),
+            Err(GameError::InvalidDiscard(stranger))
+        );
+        assert_eq!(
+            game.discard(&[hand[0], hand[0]]),
+            Err(GameError::InvalidDiscard(hand[0]))
+        );
+
+        let drawn = game.discard(&hand[..3]).unwrap();
+        let new_hand = &game.player(1).unwrap().hand();
+        assert_eq!(drawn.len(), 3);
+        assert_eq!(new_hand.len(), 5);
+        assert!(hand[..3].iter().all(|card| !new_hand.contains(card)));
+        assert!(drawn.iter().all(|card| new_hand.contains(card)));
+
+        assert_eq!(game.current_player(), Some(2));
+        assert_eq!(game.discard(&[]).unwrap(), vec![]);
+        let hand = game.player(0).unwrap().hand().to_vec();
+        game.discard(&hand).unwrap();
+
+        assert_eq!(game.street(), Street::AfterDraw);
+        assert_eq!(game.current_player(), Some(1));
+        while !game.is_hand_over() {
+            call_or_check(&mut game);
+        }
+        for seat in 0..3 {
+            let hand: [Card; 5] = game.player(seat).unwrap().hand()[..].try_into().unwrap();
+            assert_eq!(game.best_hand(seat), Some(&BestHand::new(hand)));
+        }
+        assert_eq!(total_chips(&game), 3000);
+    }
+
+    #[test]
+    fn test_draw_reshuffles_the_muck_when_the_stub_runs_out() {
+        let mut game = new_game(&[1000; 8]);
+        game.set_variant(Variant::FiveCardDraw).unwrap();
+        game.start_hand().unwrap();
+        while !game.is_drawing() {
+            call_or_check(&mut game);
+        }
+
+        let mut discarded = Vec::new();
+        while game.is_drawing() {
+            let seat = game.current_player().unwrap();
+            let hand = game.player(seat).unwrap().hand().to_vec();
+            game.discard(&hand).unwrap();
+            discarded.extend(hand);
+        }
+
+        let held: CardSet = game
+            .table()
+            .players()
+            .flat_map(|(_, player)| player.hand().to_vec())
+            .collect();
+        assert_eq!(held.len(), 40);
+        assert_eq!(discarded.len(), 40);
+        assert_eq!(game.street(), Street::AfterDraw);
+    }
+
+    #[test]
+    fn test_muck_reshuffles_replay_from_the_hand_seed() {
+        let play = || {
+            let mut game = new_game(&[1000; 8]);
+            game.set_variant(Variant::DeuceToSevenTripleDraw).unwrap();
+            game.start_hand_with_seed(21).unwrap();
+            while !game.is_hand_over() {
+                if game.is_drawing() {
+                    let seat = game.current_player().unwrap();
+                    let hand = game.player(seat).unwrap().hand().to_vec();
+                    game.discard(&hand).unwrap();
+                } else {
+                    call_or_check(&mut game);
+                }
+            }
+            (0..8)
+                .map(|seat| game.player(seat).unwrap().hand().to_vec())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(play(), play());
+    }
+
+    #[test]
+    fn test_showing_low_reads_razz_up_cards() {
+        let cards = |text| parse_cards(text).unwrap();
+        assert!(showing_low(&cards("Ac 2d")) < showing_low(&cards("3c 2c")));
+        assert!(showing_low(&cards("Kc Qd")) < showing_low(&cards("2c 2d")));
+        assert!(showing_low(&cards("5h 4h 3h")) < showing_low(&cards("6c 2d Ac")));
+    }
+
+    #[test]
+    fn test_razz_highest_card_brings_in_and_lowest_showing_acts_first() {
+        let mut game = new_game(&[1000, 1000, 1000, 1000]);
+        game.set_variant(Variant::Razz).unwrap();
+        game.set_blinds(BlindStructure {
+            ante: 5,
+            ..BlindStructure::default()
+        });
+        game.start_hand_with_seed(5).unwrap();
+
+        let up_card = |game: &Game, seat: usize| {
+            let card = game.player(seat).unwrap().up_cards()[0];
+            (
+                card.get_rank().ace_low_value(),
+                card.get_suit().tie_break_rank(),
+            )
+        };
+        let bring_in = (0..4).max_by_key(|&seat| up_card(&game, seat)).unwrap();
+        assert_eq!(game.player(bring_in).unwrap().chips, 970);
+        assert_eq!(game.current_player(), Some((bring_in + 1) % 4));
+
+        let mut street = game.street();
+        while !game.is_hand_over() {
+            if game.street() != street {
+                street = game.street();
+                let lowest_showing = (0..4)
+                    .min_by_key(|&seat| showing_low(&game.player(seat).unwrap().up_cards()))
+                    .unwrap();
+                assert_eq!(game.current_player(), Some(lowest_showing));
+            }
+            call_or_check(&mut game);
+        }
+
+        assert_eq!(street, Street::Seventh);
+        let lows: Vec<LowballHand> = (0..4)
+            .map(|seat| {
+                assert_eq!(game.best_hand(seat), None);
+                *game.best_lowball(seat).unwrap()
+            })
+            .collect();
+        let best = lows.iter().min().unwrap();
+        let winners: Vec<usize> = (0..4).filter(|&seat| lows[seat] == *best).collect();
+        assert_eq!(game.winners(), winners);
+        assert_eq!(best.lowball(), Lowball::AceToFive);
+        assert_eq!(total_chips(&game), 4000);
+    }
+
+    #[test]
+    fn test_triple_draw_bets_four_rounds_around_three_draws() {
+        let mut game = new_game(&[1000, 1000, 1000]);
+        game.set_variant(Variant::DeuceToSevenTripleDraw).unwrap();
+        game.set_betting_structure(BettingStructure::FixedLimit {
+            small_bet: 50,
+            big_bet: 100,
+            max_raises: 3,
+        });
+        game.start_hand().unwrap();
+
+        let mut rounds = vec![(game.street(), game.raise_limits())];
+        let mut draws = 0;
+        while !game.is_hand_over() {
+            if game.is_drawing() {
+                game.discard(&[]).unwrap();
+                if !game.is_drawing() {
+                    draws += 1;
+                    rounds.push((game.street(), game.raise_limits()));
+                }
+            } else {
+                call_or_check(&mut game);
+            }
+        }
+
+        assert_eq!(draws, 3);
+        assert_eq!(
+            rounds,
+            vec![
+                (Street::BeforeDraw, Some((50, 50))),
+                (Street::AfterDraw, Some((50, 50))),
+                (Street::AfterDraw, Some((100, 100))),
+                (Street::AfterDraw, Some((100, 100))),
+            ]
+        );
+        for seat in 0..3 {
+            let hand: [Card; 5] = game.player(seat).unwrap().hand()[..].try_into().unwrap();
+            assert_eq!(game.best_hand(seat), None);
+            assert_eq!(
+                game.best_lowball(seat),
+                Some(&Lowball::DeuceToSeven.evaluate(hand))
+            );
+        }
+        assert_eq!(Variant::DeuceToSevenTripleDraw.max_players(), 9);
+        assert_eq!(total_chips(&game), 3000);
+    }
+
+    #[test]
+    fn test_same_seed_replays_the_same_deal() {
+        let mut first = new_game(&[1000, 1000, 1000]);
+        let mut second = new_game(&[1000, 1000, 1000]);
+        first.set_seed(7);
+        second.set_seed(7);
+
+        for _ in 0..3 {
+            first.start_hand().unwrap();
+            second.start_hand().unwrap();
+            assert_eq!(first.hand_seed(), second.hand_seed());
+            for seat in 0..3 {
+                assert_eq!(
+                    first.player(seat).unwrap().hand(),
+                    second.player(seat).unwrap().hand()
+                );
+            }
+            for game in [&mut first, &mut second] {
+                game.act(Action::Fold).unwrap();
+                game.act(Action::Fold).unwrap();
+            }
+        }
+
+        let seed = first.hand_seed().unwrap();
+        let mut replay = new_game(&[1000, 1000, 1000]);
+        replay.start_hand_with_seed(seed).unwrap();
+        let mut original = Deck::new();
+        original.shuffle_seeded(seed);
+        let dealt = original.deal(6).unwrap();
+        assert_eq!(replay.player(0).unwrap().hand(), &dealt[4..6]);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::card::{Card, Rank, Suit};
+use crate::card::{Card, CardSet, Rank, Suit};
+use itertools::Itertools;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
 use std::fmt;
 
@@ -126,7 +128,7 @@ impl Tier {
 
         let mut card_values: Vec<Rank> = card_counts.keys().cloned().collect();
         card_values.sort();
-        return Tier::HighCard(card_values[card_values.len() - 1]);
+        Tier::HighCard(card_values[card_values.len() - 1])
     }
 }
 
@@ -153,9 +155,554 @@ impl fmt::Display for Tier {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandCategory::HighCard => "High Card",
+            HandCategory::OnePair => "One Pair",
+            HandCategory::TwoPair => "Two Pair",
+            HandCategory::ThreeOfAKind => "Three of a Kind",
+            HandCategory::Straight => "Straight",
+            HandCategory::Flush => "Flush",
+            HandCategory::FullHouse => "Full House",
+            HandCategory::FourOfAKind => "Four of a Kind",
+            HandCategory::StraightFlush => "Straight Flush",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandRank {
+    category: HandCategory,
+    ranks: [Rank; 5],
+}
+
+impl HandRank {
+    pub fn evaluate(hand: [Card; 5]) -> HandRank {
+        let mut counts = [0u8; 13];
+        for card in hand.iter() {
+            counts[card.get_rank() as usize] += 1;
+        }
+
+        let mut ranks = hand.map(|card| card.get_rank());
+        ranks.sort_by_key(|&rank| Reverse((counts[rank as usize], rank)));
+
+        let is_flush = hand
+            .iter()
+            .all(|card| card.get_suit() == hand[0].get_suit());
+        let is_wheel = ranks == [Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two];
+        let is_straight =
+            counts[ranks[0] as usize] == 1 && (ranks[0] as u8 - ranks[4] as u8 == 4 || is_wheel);
+
+        if is_wheel {
+            ranks = [Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Ace];
+        }
+
+        let category = match (counts[ranks[0] as usize], counts[ranks[3] as usize]) {
+            _ if is_straight && is_flush => HandCategory::StraightFlush,
+            (4, _) => HandCategory::FourOfAKind,
+            (3, 2) => HandCategory::FullHouse,
+            _ if is_flush => HandCategory::Flush,
+            _ if is_straight => HandCategory::Straight,
+            (3, _) => HandCategory::ThreeOfAKind,
+            (2, _) if counts[ranks[2] as usize] == 2 => HandCategory::TwoPair,
+            (2, _) => HandCategory::OnePair,
+            _ => HandCategory::HighCard,
+        };
+
+        HandRank { category, ranks }
+    }
+
+    pub fn category(&self) -> HandCategory {
+        self.category
+    }
+
+    pub fn ranks(&self) -> [Rank; 5] {
+        self.ranks
+    }
+}
+
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranks = self
+            .ranks
+            .iter()
+            .dedup()
+            .map(|rank| rank.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}({})", self.category, ranks)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BestHand {
+    pub rank: HandRank,
+    pub cards: [Card; 5],
+}
+
+impl BestHand {
+    pub fn new(cards: [Card; 5]) -> BestHand {
+        BestHand {
+            rank: HandRank::evaluate(cards),
+            cards,
+        }
+    }
+}
+
+impl PartialEq for BestHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank
+    }
+}
+
+impl Eq for BestHand {}
+
+impl Ord for BestHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank)
+    }
+}
+
+impl PartialOrd for BestHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn evaluate_best_hand(player_cards: &[Card], community_cards: &[Card]) -> BestHand {
+    let all_cards = [player_cards, community_cards].concat();
+    let mut best_hand: Option<BestHand> = None;
+
+    for combo in all_cards.iter().cloned().combinations(5) {
+        let hand_array: [Card; 5] = combo.try_into().unwrap();
+        let rank = HandRank::evaluate(hand_array);
+
+        if best_hand.as_ref().is_none_or(|best| rank > best.rank) {
+            best_hand = Some(BestHand {
+                rank,
+                cards: hand_array,
+            });
+        }
+    }
+
+    best_hand.expect("There should always be a best hand")
+}
+
+// Omaha hands use exactly two hole cards and exactly three board cards.
+pub fn evaluate_omaha_hand(player_cards: &[Card], community_cards: &[Card]) -> BestHand {
+    let mut best_hand: Option<BestHand> = None;
+
+    for hole in player_cards.iter().copied().combinations(2) {
+        for board in community_cards.iter().copied().combinations(3) {
+            let hand_array = [hole[0], hole[1], board[0], board[1], board[2]];
+            let rank = HandRank::evaluate(hand_array);
+
+            if best_hand.as_ref().is_none_or(|best| rank > best.rank) {
+                best_hand = Some(BestHand {
+                    rank,
+                    cards: hand_array,
+                });
+            }
+        }
+    }
+
+    best_hand.expect("Omaha needs two hole cards and three board cards")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LowHand {
+    ranks: [Rank; 5],
+}
+
+impl LowHand {
+    pub fn eight_or_better(hand: [Card; 5]) -> Option<LowHand> {
+        let mut ranks = hand.map(|card| card.get_rank());
+        ranks.sort_by_key(|rank| Reverse(rank.ace_low_value()));
+
+        let unpaired = ranks.windows(2).all(|pair| pair[0] != pair[1]);
+        (unpaired && ranks[0].ace_low_value() <= 8).then_some(LowHand { ranks })
+    }
+
+    pub fn ranks(&self) -> [Rank; 5] {
+        self.ranks
+    }
+
+    fn values(&self) -> [u8; 5] {
+        self.ranks.map(|rank| rank.ace_low_value())
+    }
+}
+
+// Lower lows compare as less, so the best low is the minimum.
+impl Ord for LowHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.values().cmp(&other.values())
+    }
+}
+
+impl PartialOrd for LowHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for LowHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranks = self
+            .ranks
+            .iter()
+            .map(|rank| rank.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "Low({})", ranks)
+    }
+}
+
+pub fn evaluate_best_low(player_cards: &[Card], community_cards: &[Card]) -> Option<LowHand> {
+    [player_cards, community_cards]
+        .concat()
+        .into_iter()
+        .combinations(5)
+        .filter_map(|combo| LowHand::eight_or_better(combo.try_into().unwrap()))
+        .min()
+}
+
+pub fn evaluate_omaha_low(player_cards: &[Card], community_cards: &[Card]) -> Option<LowHand> {
+    player_cards
+        .iter()
+        .copied()
+        .combinations(2)
+        .cartesian_product(community_cards.iter().copied().combinations(3))
+        .filter_map(|(hole, board)| {
+            LowHand::eight_or_better([hole[0], hole[1], board[0], board[1], board[2]])
+        })
+        .min()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lowball {
+    DeuceToSeven,
+    AceToFive,
+}
+
+impl Lowball {
+    pub fn evaluate(&self, hand: [Card; 5]) -> LowballHand {
+        let (category, ranks) = match self {
+            Lowball::DeuceToSeven => {
+                let rank = HandRank::evaluate(hand);
+                // The ace only plays high, so A-2-3-4-5 is no straight.
+                match (rank.category, rank.ranks) {
+                    (HandCategory::Straight, [Rank::Five, .., Rank::Ace]) => {
+                        (HandCategory::HighCard, WHEEL_ACE_HIGH)
+                    }
+                    (HandCategory::StraightFlush, [Rank::Five, .., Rank::Ace]) => {
+                        (HandCategory::Flush, WHEEL_ACE_HIGH)
+                    }
+                    (category, ranks) => (category, ranks),
+                }
+            }
+            Lowball::AceToFive => {
+                let counts = hand.iter().counts_by(|card| card.get_rank());
+                let mut ranks = hand.map(|card| card.get_rank());
+                ranks.sort_by_key(|rank| Reverse((counts[rank], rank.ace_low_value())));
+
+                // Straights and flushes don't count, only the pairs do.
+                let category = match (counts[&ranks[0]], counts[&ranks[3]]) {
+                    (4, _) => HandCategory::FourOfAKind,
+                    (3, 2) => HandCategory::FullHouse,
+                    (3, _) => HandCategory::ThreeOfAKind,
+                    (2, _) if counts[&ranks[2]] == 2 => HandCategory::TwoPair,
+                    (2, _) => HandCategory::OnePair,
+                    _ => HandCategory::HighCard,
+                };
+                (category, ranks)
+            }
+        };
+
+        LowballHand {
+            lowball: *self,
+            category,
+            ranks,
+        }
+    }
+}
+
+const WHEEL_ACE_HIGH: [Rank; 5] = [Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LowballHand {
+    lowball: Lowball,
+    category: HandCategory,
+    ranks: [Rank; 5],
+}
+
+impl LowballHand {
+    pub fn lowball(&self) -> Lowball {
+        self.lowball
+    }
+
+    pub fn category(&self) -> HandCategory {
+        self.category
+    }
+
+    pub fn ranks(&self) -> [Rank; 5] {
+        self.ranks
+    }
+
+    fn values(&self) -> [u8; 5] {
+        match self.lowball {
+            Lowball::DeuceToSeven => self.ranks.map(|rank| rank as u8),
+            Lowball::AceToFive => self.ranks.map(|rank| rank.ace_low_value()),
+        }
+    }
+}
+
+// As with LowHand, the better lowball hand compares as less.
+impl Ord for LowballHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.category, self.values()).cmp(&(other.category, other.values()))
+    }
+}
+
+impl PartialOrd for LowballHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for LowballHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranks = self
+            .ranks
+            .iter()
+            .dedup()
+            .map(|rank| rank.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.category {
+            HandCategory::HighCard => write!(f, "Low({})", ranks),
+            category => write!(f, "{}({})", category, ranks),
+        }
+    }
+}
+
+pub fn evaluate_best_lowball(
+    lowball: Lowball,
+    player_cards: &[Card],
+    community_cards: &[Card],
+) -> LowballHand {
+    [player_cards, community_cards]
+        .concat()
+        .into_iter()
+        .combinations(5)
+        .map(|combo| lowball.evaluate(combo.try_into().unwrap()))
+        .min()
+        .expect("There should always be a lowball hand")
+}
+
+const RANK_MASK: usize = 1 << 13;
+
+const fn build_straights() -> [u8; RANK_MASK] {
+    let mut table = [0u8; RANK_MASK];
+    let mut mask = 0;
+    while mask < RANK_MASK {
+        let mut high = 12;
+        while high >= 4 {
+            let run = 0b11111 << (high - 4);
+            if mask & run == run {
+                table[mask] = high as u8 + 1;
+                break;
+            }
+            high -= 1;
+        }
+        let wheel = 0b1_0000_0000_1111;
+        if table[mask] == 0 && mask & wheel == wheel {
+            table[mask] = 4;
+        }
+        mask += 1;
+    }
+    table
+}
+
+const fn build_top_five() -> [u32; RANK_MASK] {
+    let mut table = [0u32; RANK_MASK];
+    let mut mask = 0;
+    while mask < RANK_MASK {
+        let mut value = 0;
+        let mut taken = 0;
+        let mut rank = 13;
+        while rank > 0 && taken < 5 {
+            rank -= 1;
+            if mask & (1 << rank) != 0 {
+                value |= (rank as u32) << (16 - 4 * taken);
+                taken += 1;
+            }
+        }
+        table[mask] = value;
+        mask += 1;
+    }
+    table
+}
+
+static STRAIGHTS: [u8; RANK_MASK] = build_straights();
+static TOP_FIVE: [u32; RANK_MASK] = build_top_five();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandValue(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandValueError {
+    WrongCardCount(usize),
+    DuplicateCard(Card),
+}
+
+impl fmt::Display for HandValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandValueError::WrongCardCount(count) => {
+                write!(f, "The lookup evaluator scores 5 to 7 cards, not {}", count)
+            }
+            HandValueError::DuplicateCard(card) => write!(f, "{} appears more than once", card),
+        }
+    }
+}
+
+impl HandValue {
+    pub fn evaluate(cards: &[Card]) -> Result<HandValue, HandValueError> {
+        if !(5..=7).contains(&cards.len()) {
+            return Err(HandValueError::WrongCardCount(cards.len()));
+        }
+        let mut set = CardSet::new();
+        for &card in cards {
+            if !set.insert(card) {
+                return Err(HandValueError::DuplicateCard(card));
+            }
+        }
+        Ok(Self::evaluate_set(set))
+    }
+
+    // Callers inside the crate always pass 5 to 7 distinct cards.
+    pub(crate) fn evaluate_set(cards: CardSet) -> HandValue {
+        let suits = Suit::ALL.map(|suit| cards.rank_mask(suit) as usize);
+
+        for &suited in suits.iter() {
+            if suited.count_ones() >= 5 {
+                return match STRAIGHTS[suited] {
+                    0 => Self::with_ranks(HandCategory::Flush, TOP_FIVE[suited]),
+                    high => Self::straight(HandCategory::StraightFlush, high),
+                };
+            }
+        }
+
+        let [h, d, c, s] = suits;
+        let all = h | d | c | s;
+        let quads = h & d & c & s;
+        let odd = h ^ d ^ c ^ s;
+        let two_plus = (h & d) | (h & c) | (h & s) | (d & c) | (d & s) | (c & s);
+        let trips = odd & two_plus;
+        let pairs = two_plus & !odd & !quads;
+
+        if quads != 0 {
+            let quad = highest(quads);
+            let kicker = highest(all & !(1 << quad));
+            return Self::from_ranks(HandCategory::FourOfAKind, &[quad, quad, quad, quad, kicker]);
+        }
+
+        if trips != 0 {
+            let three = highest(trips);
+            let others = (trips & !(1 << three)) | pairs;
+            if others != 0 {
+                let two = highest(others);
+                return Self::from_ranks(HandCategory::FullHouse, &[three, three, three, two, two]);
+            }
+        }
+
+        if STRAIGHTS[all] != 0 {
+            return Self::straight(HandCategory::Straight, STRAIGHTS[all]);
+        }
+
+        if trips != 0 {
+            let three = highest(trips);
+            let rest = all & !(1 << three);
+            let first = highest(rest);
+            let second = highest(rest & !(1 << first));
+            return Self::from_ranks(
+                HandCategory::ThreeOfAKind,
+                &[three, three, three, first, second],
+            );
+        }
+
+        if pairs.count_ones() >= 2 {
+            let high = highest(pairs);
+            let low = highest(pairs & !(1 << high));
+            let kicker = highest(all & !(1 << high) & !(1 << low));
+            return Self::from_ranks(HandCategory::TwoPair, &[high, high, low, low, kicker]);
+        }
+
+        if pairs != 0 {
+            let pair = highest(pairs);
+            let kickers = TOP_FIVE[all & !(1 << pair)] >> 8;
+            return Self::with_ranks(
+                HandCategory::OnePair,
+                (pair as u32) << 16 | (pair as u32) << 12 | kickers,
+            );
+        }
+
+        Self::with_ranks(HandCategory::HighCard, TOP_FIVE[all])
+    }
+
+    pub fn category(&self) -> HandCategory {
+        match self.0 >> 20 {
+            0 => HandCategory::HighCard,
+            1 => HandCategory::OnePair,
+            2 => HandCategory::TwoPair,
+            3 => HandCategory::ThreeOfAKind,
+            4 => HandCategory::Straight,
+            5 => HandCategory::Flush,
+            6 => HandCategory::FullHouse,
+            7 => HandCategory::FourOfAKind,
+            _ => HandCategory::StraightFlush,
+        }
+    }
+
+    fn with_ranks(category: HandCategory, ranks: u32) -> HandValue {
+        HandValue((category as u32) << 20 | ranks)
+    }
+
+    fn from_ranks(category: HandCategory, ranks: &[usize; 5]) -> HandValue {
+        let packed = ranks
+            .iter()
+            .fold(0, |packed, &rank| packed << 4 | rank as u32);
+        Self::with_ranks(category, packed)
+    }
+
+    fn straight(category: HandCategory, high: u8) -> HandValue {
+        Self::with_ranks(category, (high as u32 - 1) << 16)
+    }
+}
+
+fn highest(mask: usize) -> usize {
+    usize::BITS as usize - 1 - mask.leading_zeros() as usize
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::parse_cards;
     use hamcrest2::prelude::*;
 
     #[test]
@@ -606,4 +1153,527 @@ mod tests {
         assert_that!(hand_13, equal_to(Tier::OnePair(Rank::Seven)));
         assert_that!(hand_14, equal_to(Tier::HighCard(Rank::Ace)));
     }
+
+    fn hand(cards: [(Rank, Suit); 5]) -> [Card; 5] {
+        cards.map(|(rank, suit)| Card::new(rank, suit))
+    }
+
+    fn straight_highs() -> Vec<[usize; 5]> {
+        (3..=12)
+            .rev()
+            .map(|high| {
+                if high == 3 {
+                    [3, 2, 1, 0, 12]
+                } else {
+                    [high, high - 1, high - 2, high - 3, high - 4]
+                }
+            })
+            .collect()
+    }
+
+    fn is_straight(ranks: &[usize]) -> bool {
+        ranks[0] - ranks[4] == 4 || ranks == [12, 3, 2, 1, 0]
+    }
+
+    fn reference_ordering() -> Vec<(bool, Vec<usize>)> {
+        let descending: Vec<usize> = (0..13).rev().collect();
+        let distinct: Vec<Vec<usize>> = descending
+            .iter()
+            .copied()
+            .combinations(5)
+            .filter(|ranks| !is_straight(ranks))
+            .collect();
+        let without = |excluded: &[usize]| -> Vec<usize> {
+            descending
+                .iter()
+                .copied()
+                .filter(|rank| !excluded.contains(rank))
+                .collect()
+        };
+
+        let mut classes = Vec::new();
+        for ranks in straight_highs() {
+            classes.push((true, ranks.to_vec()));
+        }
+        for &quad in &descending {
+            for kicker in without(&[quad]) {
+                classes.push((false, vec![quad, quad, quad, quad, kicker]));
+            }
+        }
+        for &trips in &descending {
+            for pair in without(&[trips]) {
+                classes.push((false, vec![trips, trips, trips, pair, pair]));
+            }
+        }
+        for ranks in &distinct {
+            classes.push((true, ranks.clone()));
+        }
+        for ranks in straight_highs() {
+            classes.push((false, ranks.to_vec()));
+        }
+        for &trips in &descending {
+            for kickers in without(&[trips]).into_iter().combinations(2) {
+                classes.push((false, vec![trips, trips, trips, kickers[0], kickers[1]]));
+            }
+        }
+        for pairs in descending.iter().copied().combinations(2) {
+            for kicker in without(&pairs) {
+                classes.push((false, vec![pairs[0], pairs[0], pairs[1], pairs[1], kicker]));
+            }
+        }
+        for &pair in &descending {
+            for kickers in without(&[pair]).into_iter().combinations(3) {
+                classes.push((false, vec![pair, pair, kickers[0], kickers[1], kickers[2]]));
+            }
+        }
+        for ranks in &distinct {
+            classes.push((false, ranks.clone()));
+        }
+
+        classes
+    }
+
+    fn representative(is_flush: bool, ranks: &[usize]) -> [Card; 5] {
+        let mut cards = Vec::with_capacity(5);
+        for (position, &rank) in ranks.iter().enumerate() {
+            let copies = ranks[..position].iter().filter(|&&r| r == rank).count();
+            let suit = if is_flush {
+                Suit::Hearts
+            } else if copies == 0 && position == 4 {
+                Suit::Diamonds
+            } else {
+                Suit::ALL[copies]
+            };
+            cards.push(Card::new(Rank::ALL[rank], suit));
+        }
+        cards.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_hand_rank_matches_reference_ordering() {
+        let classes = reference_ordering();
+        assert_that!(classes.len(), equal_to(7462));
+
+        let ranks: Vec<HandRank> = classes
+            .iter()
+            .map(|(is_flush, ranks)| HandRank::evaluate(representative(*is_flush, ranks)))
+            .collect();
+
+        for (better, worse) in ranks.iter().tuple_windows() {
+            assert!(better > worse, "{} should beat {}", better, worse);
+        }
+
+        let count = |category| ranks.iter().filter(|r| r.category() == category).count();
+        assert_that!(count(HandCategory::StraightFlush), equal_to(10));
+        assert_that!(count(HandCategory::FourOfAKind), equal_to(156));
+        assert_that!(count(HandCategory::FullHouse), equal_to(156));
+        assert_that!(count(HandCategory::Flush), equal_to(1277));
+        assert_that!(count(HandCategory::Straight), equal_to(10));
+        assert_that!(count(HandCategory::ThreeOfAKind), equal_to(858));
+        assert_that!(count(HandCategory::TwoPair), equal_to(858));
+        assert_that!(count(HandCategory::OnePair), equal_to(2860));
+        assert_that!(count(HandCategory::HighCard), equal_to(1277));
+    }
+
+    #[test]
+    fn test_hand_rank_ignores_card_order() {
+        for (is_flush, ranks) in reference_ordering() {
+            let mut cards = representative(is_flush, &ranks);
+            let expected = HandRank::evaluate(cards);
+            for _ in 0..4 {
+                cards.rotate_left(1);
+                cards.swap(0, 3);
+                assert_that!(HandRank::evaluate(cards), equal_to(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hand_rank_kickers() {
+        let aces_king_kicker = HandRank::evaluate(hand([
+            (Rank::Ace, Suit::Clubs),
+            (Rank::Ace, Suit::Spades),
+            (Rank::King, Suit::Hearts),
+            (Rank::Four, Suit::Diamonds),
+            (Rank::Three, Suit::Diamonds),
+        ]));
+        let aces_queen_kicker = HandRank::evaluate(hand([
+            (Rank::Ace, Suit::Hearts),
+            (Rank::Ace, Suit::Diamonds),
+            (Rank::Queen, Suit::Hearts),
+            (Rank::Jack, Suit::Diamonds),
+            (Rank::Ten, Suit::Clubs),
+        ]));
+        let two_pair_ace_kicker = HandRank::evaluate(hand([
+            (Rank::King, Suit::Clubs),
+            (Rank::King, Suit::Spades),
+            (Rank::Three, Suit::Hearts),
+            (Rank::Three, Suit::Diamonds),
+            (Rank::Ace, Suit::Diamonds),
+        ]));
+        let two_pair_queen_kicker = HandRank::evaluate(hand([
+            (Rank::King, Suit::Hearts),
+            (Rank::King, Suit::Diamonds),
+            (Rank::Three, Suit::Clubs),
+            (Rank::Three, Suit::Spades),
+            (Rank::Queen, Suit::Diamonds),
+        ]));
+
+        assert_that!(aces_king_kicker > aces_queen_kicker, is(true));
+        assert_that!(two_pair_ace_kicker > two_pair_queen_kicker, is(true));
+        assert_that!(
+            two_pair_ace_kicker.category(),
+            equal_to(HandCategory::TwoPair)
+        );
+    }
+
+    #[test]
+    fn test_hand_rank_flushes_ignore_suit() {
+        let king_high_clubs = HandRank::evaluate(hand([
+            (Rank::King, Suit::Clubs),
+            (Rank::Nine, Suit::Clubs),
+            (Rank::Seven, Suit::Clubs),
+            (Rank::Four, Suit::Clubs),
+            (Rank::Two, Suit::Clubs),
+        ]));
+        let king_high_spades = HandRank::evaluate(hand([
+            (Rank::King, Suit::Spades),
+            (Rank::Nine, Suit::Spades),
+            (Rank::Seven, Suit::Spades),
+            (Rank::Four, Suit::Spades),
+            (Rank::Two, Suit::Spades),
+        ]));
+        let queen_high_spades = HandRank::evaluate(hand([
+            (Rank::Queen, Suit::Spades),
+            (Rank::Jack, Suit::Spades),
+            (Rank::Nine, Suit::Spades),
+            (Rank::Four, Suit::Spades),
+            (Rank::Two, Suit::Spades),
+        ]));
+
+        assert_that!(king_high_clubs, equal_to(king_high_spades));
+        assert_that!(king_high_clubs > queen_high_spades, is(true));
+    }
+
+    #[test]
+    fn test_hand_rank_wheel_is_lowest_straight() {
+        let wheel = HandRank::evaluate(hand([
+            (Rank::Ace, Suit::Clubs),
+            (Rank::Two, Suit::Spades),
+            (Rank::Three, Suit::Hearts),
+            (Rank::Four, Suit::Diamonds),
+            (Rank::Five, Suit::Diamonds),
+        ]));
+        let six_high = HandRank::evaluate(hand([
+            (Rank::Six, Suit::Clubs),
+            (Rank::Two, Suit::Spades),
+            (Rank::Three, Suit::Hearts),
+            (Rank::Four, Suit::Diamonds),
+            (Rank::Five, Suit::Diamonds),
+        ]));
+
+        assert_that!(wheel.category(), equal_to(HandCategory::Straight));
+        assert_that!(wheel.ranks()[0], equal_to(Rank::Five));
+        assert_that!(six_high > wheel, is(true));
+    }
+
+    #[test]
+    fn test_best_hand_ignores_input_order() {
+        let hole = [
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Clubs),
+        ];
+        let board = [
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Diamonds),
+            Card::new(Rank::Seven, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Spades),
+        ];
+        let mut reversed = board;
+        reversed.reverse();
+
+        let best = evaluate_best_hand(&hole, &board);
+        assert_that!(evaluate_best_hand(&hole, &reversed), equal_to(best.clone()));
+        assert_that!(
+            best.rank.ranks(),
+            equal_to([Rank::Ace, Rank::Ace, Rank::King, Rank::Nine, Rank::Seven])
+        );
+    }
+
+    #[test]
+    fn test_omaha_uses_exactly_two_hole_cards() {
+        let hole = parse_cards("As Ks Qs Js").unwrap();
+        let board = parse_cards("Ts 9s 8d 3c 4h").unwrap();
+
+        let holdem = evaluate_best_hand(&hole, &board);
+        let omaha = evaluate_omaha_hand(&hole, &board);
+        assert_that!(
+            holdem.rank.category(),
+            equal_to(HandCategory::StraightFlush)
+        );
+        assert_that!(omaha.rank.category(), equal_to(HandCategory::Straight));
+        assert_that!(
+            omaha.rank.ranks(),
+            equal_to([Rank::Queen, Rank::Jack, Rank::Ten, Rank::Nine, Rank::Eight])
+        );
+    }
+
+    #[test]
+    fn test_omaha_cannot_play_the_board() {
+        let hole = parse_cards("2c 3d 7h 8s Jd 4c").unwrap();
+        let board = parse_cards("Ah Kh Qh Jh Th").unwrap();
+
+        let best = evaluate_omaha_hand(&hole, &board);
+        assert_that!(best.rank.category(), equal_to(HandCategory::OnePair));
+        assert_that!(best.rank.ranks()[0], equal_to(Rank::Jack));
+        assert_that!(
+            best.cards.iter().filter(|card| hole.contains(card)).count(),
+            equal_to(2)
+        );
+    }
+
+    fn low(text: &str) -> Option<LowHand> {
+        LowHand::eight_or_better(parse_cards(text).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn test_eight_or_better_low() {
+        let wheel = low("5c 4d 3h 2s Ac").unwrap();
+        assert_that!(
+            wheel.ranks(),
+            equal_to([Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Ace])
+        );
+        assert_that!(
+            wheel.to_string(),
+            equal_to("Low(5, 4, 3, 2, A)".to_string())
+        );
+
+        assert_that!(low("7h 5h 4h 3h 2h") < low("8c 4d 3h 2s Ac"), is(true));
+        assert_that!(low("8c 6d 4h 2s Ac") < low("8c 7d 3h 2s Ac"), is(true));
+        assert_that!(low("8c 6d 4h 2s Ac").is_some(), is(true));
+        assert_that!(low("9c 4d 3h 2s Ac"), equal_to(None));
+        assert_that!(low("8c 4d 4h 2s Ac"), equal_to(None));
+        assert_that!(low("Kc 4d 3h 2s Ac"), equal_to(None));
+    }
+
+    #[test]
+    fn test_best_low_from_seven_cards() {
+        let hole = parse_cards("Ah 2d").unwrap();
+        let board = parse_cards("3c 7s 8h Kd Ks").unwrap();
+        assert_that!(
+            evaluate_best_low(&hole, &board).unwrap().ranks(),
+            equal_to([Rank::Eight, Rank::Seven, Rank::Three, Rank::Two, Rank::Ace])
+        );
+        assert_that!(
+            evaluate_best_low(&parse_cards("Kh Kc").unwrap(), &board),
+            equal_to(None)
+        );
+    }
+
+    #[test]
+    fn test_omaha_low_needs_two_hole_cards() {
+        let board = parse_cards("Ac 2c 3d 4h 5s").unwrap();
+        assert_that!(
+            evaluate_omaha_low(&parse_cards("Kh Kd Qs Qc").unwrap(), &board),
+            equal_to(None)
+        );
+        assert_that!(
+            evaluate_omaha_low(&parse_cards("Ah Kd Kh Qc").unwrap(), &board),
+            equal_to(None)
+        );
+
+        let low = evaluate_omaha_low(&parse_cards("6h 7d Kh Qc").unwrap(), &board).unwrap();
+        assert_that!(
+            low.ranks(),
+            equal_to([Rank::Seven, Rank::Six, Rank::Three, Rank::Two, Rank::Ace])
+        );
+    }
+
+    fn lowball(lowball: Lowball, text: &str) -> LowballHand {
+        lowball.evaluate(parse_cards(text).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn test_deuce_to_seven_lowball() {
+        let deuce = |text| lowball(Lowball::DeuceToSeven, text);
+        let number_one = deuce("7c 5d 4h 3s 2c");
+        assert_that!(number_one.category(), equal_to(HandCategory::HighCard));
+        assert_that!(
+            number_one.to_string(),
+            equal_to("Low(7, 5, 4, 3, 2)".to_string())
+        );
+
+        assert_that!(number_one < deuce("7c 6d 4h 3s 2c"), is(true));
+        assert_that!(deuce("8c 6d 4h 3s 2c") < deuce("8c 6d 5h 3s 2c"), is(true));
+        assert_that!(deuce("Kc Qd Jh 9s 8c") < deuce("2c 2d 3h 4s 5c"), is(true));
+
+        // Straights and flushes count against the hand.
+        assert_that!(deuce("6c 5d 4h 3s 2c") > deuce("Kc Qd Jh 9s 8c"), is(true));
+        assert_that!(deuce("7h 5h 4h 3h 2h") > deuce("Ac Kd Qh Js 9c"), is(true));
+
+        // The ace is high, so A-2-3-4-5 is just an ace-high hand.
+        let wheel = deuce("Ac 2d 3h 4s 5c");
+        assert_that!(wheel.category(), equal_to(HandCategory::HighCard));
+        assert_that!(
+            wheel.ranks(),
+            equal_to([Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two])
+        );
+        assert_that!(wheel > deuce("Kc Qd Jh 9s 8c"), is(true));
+        assert_that!(
+            deuce("Ah 2h 3h 4h 5h").category(),
+            equal_to(HandCategory::Flush)
+        );
+    }
+
+    #[test]
+    fn test_ace_to_five_lowball() {
+        let razz = |text| lowball(Lowball::AceToFive, text);
+        let wheel = razz("5h 4h 3h 2h Ah");
+        assert_that!(wheel.category(), equal_to(HandCategory::HighCard));
+        assert_that!(
+            wheel.to_string(),
+            equal_to("Low(5, 4, 3, 2, A)".to_string())
+        );
+
+        assert_that!(wheel < razz("6c 4d 3h 2s Ac"), is(true));
+        assert_that!(razz("6c 4d 3h 2s Ac") < razz("6c 5d 3h 2s Ac"), is(true));
+        assert_that!(razz("Kc Qd Jh Ts 9c") < razz("Ac Ad 2h 3s 4c"), is(true));
+        assert_that!(razz("Ac Ad 2h 3s 4c") < razz("2c 2d 3h 4s 5c"), is(true));
+        assert_that!(razz("3c 3d 2h 2s 4c") > razz("Kc Kd Qh Js 9c"), is(true));
+
+        let pair = razz("Kc 2d 3h 2s 4c");
+        assert_that!(pair.category(), equal_to(HandCategory::OnePair));
+        assert_that!(
+            pair.to_string(),
+            equal_to("One Pair(2, K, 4, 3)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_lowball_from_seven_cards() {
+        let hole = parse_cards("Kh Kd Ac").unwrap();
+        let board = parse_cards("As 2c 3d 9h").unwrap();
+
+        let razz = evaluate_best_lowball(Lowball::AceToFive, &hole, &board);
+        assert_that!(
+            razz.ranks(),
+            equal_to([Rank::King, Rank::Nine, Rank::Three, Rank::Two, Rank::Ace])
+        );
+
+        // Deuce-to-seven has to keep the ace, as high card, to avoid a pair.
+        let deuce = evaluate_best_lowball(Lowball::DeuceToSeven, &hole, &board);
+        assert_that!(deuce.category(), equal_to(HandCategory::HighCard));
+        assert_that!(
+            deuce.ranks(),
+            equal_to([Rank::Ace, Rank::King, Rank::Nine, Rank::Three, Rank::Two])
+        );
+    }
+
+    fn full_deck() -> Vec<Card> {
+        Suit::ALL
+            .iter()
+            .flat_map(|&suit| Rank::ALL.iter().map(move |&rank| Card::new(rank, suit)))
+            .collect()
+    }
+
+    fn assert_consistent(values: &std::collections::BTreeMap<HandRank, HandValue>) {
+        for ((rank, value), (next_rank, next_value)) in values.iter().tuple_windows() {
+            assert!(
+                value < next_value,
+                "{} scored {:?} but {} scored {:?}",
+                rank,
+                value,
+                next_rank,
+                next_value
+            );
+        }
+    }
+
+    #[test]
+    fn test_hand_value_matches_hand_rank_on_every_five_card_hand() {
+        let deck = full_deck();
+        let mut values = std::collections::BTreeMap::new();
+
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let hand = [deck[a], deck[b], deck[c], deck[d], deck[e]];
+                            let value = HandValue::evaluate(&hand).unwrap();
+                            let previous = values.insert(HandRank::evaluate(hand), value);
+                            assert!(previous.is_none_or(|previous| previous == value));
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_that!(values.len(), equal_to(7462));
+        assert_consistent(&values);
+    }
+
+    #[test]
+    fn test_hand_value_matches_best_hand_on_six_and_seven_cards() {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        let mut deck = full_deck();
+        let mut rng = StdRng::seed_from_u64(7462);
+        let mut values = std::collections::BTreeMap::new();
+
+        for count in [6, 7] {
+            for _ in 0..20_000 {
+                deck.shuffle(&mut rng);
+                let cards = &deck[..count];
+                let value = HandValue::evaluate(cards).unwrap();
+                let best = evaluate_best_hand(&cards[..2], &cards[2..]);
+
+                assert_that!(value.category(), equal_to(best.rank.category()));
+                let previous = values.insert(best.rank, value);
+                assert!(previous.is_none_or(|previous| previous == value));
+            }
+        }
+
+        assert_consistent(&values);
+    }
+
+    #[test]
+    fn test_hand_value_flush_beats_straight_in_seven_cards() {
+        let cards = [
+            Card::new(Rank::Six, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Eight, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Ten, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::King, Suit::Hearts),
+        ];
+
+        assert_that!(
+            HandValue::evaluate(&cards).unwrap().category(),
+            equal_to(HandCategory::Flush)
+        );
+    }
+
+    #[test]
+    fn test_hand_value_rejects_bad_card_counts_and_duplicates() {
+        let cards = parse_cards("As Ks Qs Js Ts 9s 8s 7s").unwrap();
+        assert_that!(
+            HandValue::evaluate(&cards[..4]),
+            equal_to(Err(HandValueError::WrongCardCount(4)))
+        );
+        assert_that!(
+            HandValue::evaluate(&cards),
+            equal_to(Err(HandValueError::WrongCardCount(8)))
+        );
+
+        let doubled = [cards[0], cards[1], cards[2], cards[3], cards[0]];
+        assert_that!(
+            HandValue::evaluate(&doubled),
+            equal_to(Err(HandValueError::DuplicateCard(doubled[0])))
+        );
+    }
 }
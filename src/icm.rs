@@ -0,0 +1,285 @@
+use crate::table::Table;
+use std::fmt;
+
+pub const MAX_ICM_PLAYERS: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IcmError {
+    NoPlayers,
+    TooManyPlayers(usize),
+    EmptyStack(usize),
+    InvalidPayout(f64),
+    InvalidLeftToPlay(f64),
+}
+
+impl fmt::Display for IcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcmError::NoPlayers => write!(f, "There are no stacks to value"),
+            IcmError::TooManyPlayers(count) => write!(
+                f,
+                "ICM handles at most {} players, not {}",
+                MAX_ICM_PLAYERS, count
+            ),
+            IcmError::EmptyStack(index) => write!(f, "Stack {} has no chips", index),
+            IcmError::InvalidPayout(amount) => write!(f, "{} is not a valid payout", amount),
+            IcmError::InvalidLeftToPlay(amount) => {
+                write!(f, "Cannot leave {} to play for", amount)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PayoutStructure {
+    payouts: Vec<f64>,
+}
+
+impl PayoutStructure {
+    pub fn new(payouts: Vec<f64>) -> Result<Self, IcmError> {
+        if let Some(&amount) = payouts
+            .iter()
+            .find(|amount| !amount.is_finite() || **amount < 0.0)
+        {
+            return Err(IcmError::InvalidPayout(amount));
+        }
+        if let Some(pair) = payouts.windows(2).find(|pair| pair[0] < pair[1]) {
+            return Err(IcmError::InvalidPayout(pair[1]));
+        }
+        Ok(Self { payouts })
+    }
+
+    pub fn from_percentages(prize_pool: f64, percentages: &[f64]) -> Result<Self, IcmError> {
+        Self::new(
+            percentages
+                .iter()
+                .map(|percentage| prize_pool * percentage / 100.0)
+                .collect(),
+        )
+    }
+
+    pub fn payouts(&self) -> &[f64] {
+        &self.payouts
+    }
+
+    pub fn payout(&self, place: usize) -> f64 {
+        self.payouts.get(place).copied().unwrap_or(0.0)
+    }
+
+    pub fn total(&self) -> f64 {
+        self.payouts.iter().sum()
+    }
+}
+
+// Malmuth-Harville: a player finishes first with probability proportional to
+// their stack, then the same rule is applied to the players who are left.
+pub fn icm_equity(stacks: &[u32], payouts: &PayoutStructure) -> Result<Vec<f64>, IcmError> {
+    check_stacks(stacks)?;
+
+    let count = stacks.len();
+    let paid_places = payouts.payouts().len().min(count);
+    let total: u64 = stacks.iter().map(|&stack| u64::from(stack)).sum();
+
+    let mut equity = vec![0.0; count];
+    let mut reach = vec![0.0; 1 << count];
+    reach[0] = 1.0;
+
+    for placed in 0..(1usize << count) {
+        let probability = reach[placed];
+        let place = placed.count_ones() as usize;
+        if probability == 0.0 || place >= paid_places {
+            continue;
+        }
+
+        let remaining = total
+            - (0..count)
+                .filter(|&player| placed & (1 << player) != 0)
+                .map(|player| u64::from(stacks[player]))
+                .sum::<u64>();
+        for player in (0..count).filter(|&player| placed & (1 << player) == 0) {
+            let chance = probability * f64::from(stacks[player]) / remaining as f64;
+            equity[player] += chance * payouts.payout(place);
+            reach[placed | (1 << player)] += chance;
+        }
+    }
+
+    Ok(equity)
+}
+
+fn check_stacks(stacks: &[u32]) -> Result<(), IcmError> {
+    if stacks.is_empty() {
+        return Err(IcmError::NoPlayers);
+    }
+    if stacks.len() > MAX_ICM_PLAYERS {
+        return Err(IcmError::TooManyPlayers(stacks.len()));
+    }
+    if let Some(index) = stacks.iter().position(|&stack| stack == 0) {
+        return Err(IcmError::EmptyStack(index));
+    }
+    Ok(())
+}
+
+pub fn table_equity(
+    table: &Table,
+    payouts: &PayoutStructure,
+) -> Result<Vec<(usize, f64)>, IcmError> {
+    let (seats, stacks): (Vec<usize>, Vec<u32>) = table
+        .players()
+        .filter(|(_, player)| !player.is_busted())
+        .map(|(seat, player)| (seat, player.chips))
+        .unzip();
+    let equity = icm_equity(&stacks, payouts)?;
+    Ok(seats.into_iter().zip(equity).collect())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DealMethod {
+    Icm,
+    ChipChop,
+}
+
+pub fn chop(
+    stacks: &[u32],
+    payouts: &PayoutStructure,
+    method: DealMethod,
+    left_to_play: f64,
+) -> Result<Vec<f64>, IcmError> {
+    let first = payouts.payout(0);
+    let second = payouts.payout(1).min(first);
+    if !left_to_play.is_finite() || left_to_play < 0.0 || left_to_play > first - second {
+        return Err(IcmError::InvalidLeftToPlay(left_to_play));
+    }
+
+    let mut remaining = payouts.payouts()[..payouts.payouts().len().min(stacks.len())].to_vec();
+    if let Some(first) = remaining.first_mut() {
+        *first -= left_to_play;
+    }
+    let remaining = PayoutStructure::new(remaining)?;
+
+    match method {
+        DealMethod::Icm => icm_equity(stacks, &remaining),
+        DealMethod::ChipChop => {
+            check_stacks(stacks)?;
+            let floor = remaining.payout(stacks.len() - 1);
+            let pool = remaining.total() - floor * stacks.len() as f64;
+            let total: u64 = stacks.iter().map(|&stack| u64::from(stack)).sum();
+            Ok(stacks
+                .iter()
+                .map(|&stack| floor + pool * f64::from(stack) / total as f64)
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-9,
+                "{:?} != {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_equal_stacks_split_evenly() {
+        let payouts = PayoutStructure::new(vec![70.0, 30.0]).unwrap();
+        assert_close(&icm_equity(&[500, 500], &payouts).unwrap(), &[50.0, 50.0]);
+    }
+
+    #[test]
+    fn test_three_handed_equity() {
+        let payouts = PayoutStructure::from_percentages(100.0, &[50.0, 30.0, 20.0]).unwrap();
+        let equity = icm_equity(&[5000, 3000, 2000], &payouts).unwrap();
+
+        assert_close(&equity, &[38.392857142857146, 32.75, 28.857142857142858]);
+        assert!((equity.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_more_players_than_paid_places() {
+        let payouts = PayoutStructure::new(vec![500.0, 300.0, 200.0]).unwrap();
+        let equity = icm_equity(&[1000, 2000, 3000, 4000], &payouts).unwrap();
+
+        assert_close(
+            &equity,
+            &[
+                133.21428571428572,
+                235.87301587301587,
+                294.88095238095235,
+                336.031746031746,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let payouts = PayoutStructure::new(vec![70.0, 30.0]).unwrap();
+        assert_eq!(icm_equity(&[], &payouts), Err(IcmError::NoPlayers));
+        assert_eq!(
+            icm_equity(&[100, 0], &payouts),
+            Err(IcmError::EmptyStack(1))
+        );
+        assert_eq!(
+            icm_equity(&[1; 21], &payouts),
+            Err(IcmError::TooManyPlayers(21))
+        );
+        assert_eq!(
+            PayoutStructure::new(vec![30.0, 70.0]),
+            Err(IcmError::InvalidPayout(70.0))
+        );
+        assert_eq!(
+            PayoutStructure::new(vec![-1.0]),
+            Err(IcmError::InvalidPayout(-1.0))
+        );
+    }
+
+    #[test]
+    fn test_equity_from_a_table() {
+        let players = vec![
+            Player::new("Dusan", 5000),
+            Player::new("Ana", 0),
+            Player::new("Marko", 3000),
+            Player::new("Jelena", 2000),
+        ];
+        let table = Table::with_players(players).unwrap();
+        let payouts = PayoutStructure::new(vec![50.0, 30.0, 20.0]).unwrap();
+
+        let equity = table_equity(&table, &payouts).unwrap();
+        assert_eq!(
+            equity.iter().map(|&(seat, _)| seat).collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+        assert!((equity[0].1 - 38.392857142857146).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_icm_chop_with_money_left_to_play() {
+        let payouts = PayoutStructure::new(vec![50.0, 30.0, 20.0]).unwrap();
+        let stacks = [5000, 3000, 2000];
+
+        let deal = chop(&stacks, &payouts, DealMethod::Icm, 0.0).unwrap();
+        assert_close(&deal, &icm_equity(&stacks, &payouts).unwrap());
+
+        let deal = chop(&stacks, &payouts, DealMethod::Icm, 10.0).unwrap();
+        assert!((deal.iter().sum::<f64>() - 90.0).abs() < 1e-9);
+        assert_eq!(
+            chop(&stacks, &payouts, DealMethod::Icm, 25.0),
+            Err(IcmError::InvalidLeftToPlay(25.0))
+        );
+    }
+
+    #[test]
+    fn test_chip_chop() {
+        let payouts = PayoutStructure::new(vec![50.0, 30.0, 20.0]).unwrap();
+        let deal = chop(&[5000, 3000, 2000], &payouts, DealMethod::ChipChop, 0.0).unwrap();
+        assert_close(&deal, &[40.0, 32.0, 28.0]);
+    }
+}
@@ -1,4 +1,11 @@
 pub mod betting;
 pub mod card;
+pub mod director;
+pub mod equity;
+pub mod game;
 pub mod hand_eval;
+pub mod icm;
 pub mod player;
+pub mod range;
+pub mod table;
+pub mod tournament;
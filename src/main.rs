@@ -1,173 +1,494 @@
-use itertools::Itertools;
-use poker_game::card::{Card, Deck};
-use poker_game::hand_eval::Tier;
-use std::cmp::Ordering;
+use poker_game::betting::{Action, BettingStructure};
+use poker_game::card::{parse_cards, Card, Rank};
+use poker_game::game::{Game, Variant};
+use poker_game::hand_eval::{HandCategory, Lowball};
+use poker_game::player::Player;
+use poker_game::table::Table;
+use poker_game::tournament::{BlindSchedule, Tournament};
+use std::io::{self, BufRead, Write};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct BestHand {
-    pub tier: Tier,
-    pub cards: [Card; 5],
-}
+const STARTING_CHIPS: u32 = 1000;
+const DEFAULT_SCHEDULE: &str = "
+    hands
+    25/50 10
+    50/100 10
+    75/150/25 10
+    100/200/25 10
+    150/300/50 10
+    200/400/50 10
+";
+
+fn main() {
+    let seed = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<u64>().ok())
+        .unwrap_or_else(rand::random);
 
-impl Ord for BestHand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.tier.cmp(&other.tier) {
-            Ordering::Equal => self.cards.cmp(&other.cards),
-            other => other,
+    let text = match std::env::args().nth(2) {
+        Some(path) => match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("Could not read {}: {}", path, error);
+                return;
+            }
+        },
+        None => DEFAULT_SCHEDULE.to_string(),
+    };
+    let schedule = match text.parse::<BlindSchedule>() {
+        Ok(schedule) => schedule,
+        Err(error) => {
+            println!("Invalid blind schedule: {}", error);
+            return;
         }
-    }
-}
+    };
 
-impl PartialOrd for BestHand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+    let mut input = io::stdin().lock().lines().map_while(Result::ok);
 
-fn main() {
-    let mut deck = Deck::new();
-    deck.shuffle();
+    let Some(seat_count) = prompt_seat_count(&mut input) else {
+        return;
+    };
 
-    let player1_hand = deck.deal(2).expect("Not enough cards for Player 1");
-    let player2_hand = deck.deal(2).expect("Not enough cards for Player 2");
+    let mut names = Vec::with_capacity(seat_count);
+    let mut is_bot = Vec::with_capacity(seat_count);
+    for seat in 0..seat_count {
+        let Some(name) = prompt(&mut input, &format!("Name for seat {}: ", seat + 1)) else {
+            return;
+        };
+        let name = if name.is_empty() {
+            format!("Player {}", seat + 1)
+        } else {
+            name
+        };
+        let Some(kind) = prompt(&mut input, "Human or bot? [h/b]: ") else {
+            return;
+        };
+        names.push(name);
+        is_bot.push(kind.eq_ignore_ascii_case("b") || kind.eq_ignore_ascii_case("bot"));
+    }
 
-    let community_cards = deck.deal(5).expect("Not enough cards for community cards");
+    let Some(variant) = prompt_variant(&mut input, seat_count) else {
+        return;
+    };
+    let Some(structure) = prompt_betting_structure(&mut input) else {
+        return;
+    };
+
+    let players = names
+        .iter()
+        .map(|name| Player::new(name, STARTING_CHIPS))
+        .collect();
+    let table = Table::with_players(players).expect("2 to 9 seats fit at a table");
+    let mut tournament = Tournament::new(table, schedule);
+    tournament.set_seed(seed);
+    tournament.set_betting_structure(structure);
+    tournament
+        .set_variant(variant)
+        .expect("No hand has started yet");
 
-    println!(
-        "Player 1's cards: [{}]",
-        player1_hand
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-    println!();
-    println!(
-        "Player 2's cards: [{}]",
-        player2_hand
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-    println!();
-    println!(
-        "Community cards: [{}]",
-        community_cards
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
     println!();
-    println!("Evaluating best hands...\n");
+    println!("Seed: {}", seed);
+
+    let mut level = 0;
+    while !tournament.is_over() {
+        tournament
+            .start_hand()
+            .expect("At least two players have chips");
+        let game = tournament.game();
 
-    let player1_best_hand = evaluate_best_hand(&player1_hand, &community_cards);
-    let player1_best_tier = player1_best_hand.tier;
-    let player1_best_hand_cards = player1_best_hand.cards;
+        if tournament.level_number() != level {
+            level = tournament.level_number();
+            println!();
+            println!("--- Level {}: blinds {} ---", level, tournament.level());
+        }
 
-    let player2_best_hand = evaluate_best_hand(&player2_hand, &community_cards);
-    let player2_best_tier = player2_best_hand.tier;
-    let player2_best_hand_cards = player2_best_hand.cards;
+        println!();
+        println!(
+            "=== Hand #{} (seed {}) ===",
+            tournament.hands_played() + 1,
+            game.hand_seed().unwrap_or(seed)
+        );
+        print_stacks(game);
+
+        let mut street = None;
+        while let Some(seat) = tournament.game().current_player() {
+            let game = tournament.game();
+            if street != Some(game.street()) {
+                street = Some(game.street());
+                print_street(game);
+            }
+
+            let player = game.player(seat).expect("The acting seat is occupied");
+            if game.is_drawing() {
+                let name = player.name;
+                let discards = if is_bot[seat] {
+                    bot_discards(game.variant(), player.hand())
+                } else {
+                    println!();
+                    println!("{} to draw: cards [{}]", name, join_cards(player.hand()));
+                    let Some(discards) = prompt_discards(&mut input) else {
+                        println!("Goodbye!");
+                        return;
+                    };
+                    discards
+                };
+                match tournament.discard(&discards) {
+                    Ok(drawn) => println!("{} draws {}", name, drawn.len()),
+                    Err(error) => println!("{}", error),
+                }
+                continue;
+            }
+
+            let action = if is_bot[seat] {
+                let action = bot_action(game, seat);
+                println!("{} {}", player.name, describe(action));
+                action
+            } else {
+                println!();
+                let raise = match game.raise_limits() {
+                    Some((min, max)) if min == max => format!(", raise {}", min),
+                    Some((min, max)) => format!(", raise {} to {}", min, max),
+                    None => String::new(),
+                };
+                println!(
+                    "{} to act: cards [{}], to call {}{}, stack {}",
+                    player.name,
+                    join_cards(player.hand()),
+                    game.to_call(),
+                    raise,
+                    player.chips
+                );
+                let Some(action) = prompt_action(&mut input, game) else {
+                    println!("Goodbye!");
+                    return;
+                };
+                action
+            };
+
+            if let Err(error) = tournament.act(action) {
+                println!("{}", error);
+                if is_bot[seat] {
+                    let fallback = if tournament.game().to_call() == 0 {
+                        Action::Check
+                    } else {
+                        Action::Fold
+                    };
+                    tournament
+                        .act(fallback)
+                        .expect("Checking or folding is always legal");
+                }
+            }
+        }
+
+        print_result(tournament.game());
+        let finishes = tournament.finish_hand().expect("The hand is over");
+        for finish in finishes.iter().filter(|finish| finish.position > 1) {
+            println!("{} finishes in place {}", finish.name, finish.position);
+        }
+    }
 
-    println!(
-        "Player 1's best hand: {} with [{}]",
-        player1_best_tier,
-        player1_best_hand_cards
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
     println!();
-    println!(
-        "Player 2's best hand: {} with [{}]",
-        player2_best_tier,
-        player2_best_hand_cards
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-    println!("\nComparing hands...\n");
-
-    match player1_best_tier.cmp(&player2_best_tier) {
-        std::cmp::Ordering::Greater => {
-            println!(
-                "Player 1 wins with {} [{}]!",
-                player1_best_tier,
-                player1_best_hand_cards
-                    .iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
+    println!("Final standings:");
+    for finish in tournament.results() {
+        println!("{}. {}", finish.position, finish.name);
+    }
+}
+
+fn prompt(input: &mut impl Iterator<Item = String>, message: &str) -> Option<String> {
+    print!("{}", message);
+    io::stdout().flush().ok();
+    input.next().map(|line| line.trim().to_string())
+}
+
+fn prompt_seat_count(input: &mut impl Iterator<Item = String>) -> Option<usize> {
+    loop {
+        let answer = prompt(input, "How many players (2-9)? ")?;
+        match answer.parse::<usize>() {
+            Ok(count) if (2..=9).contains(&count) => return Some(count),
+            _ => println!("Please enter a number from 2 to 9."),
         }
-        std::cmp::Ordering::Less => {
+    }
+}
+
+fn prompt_variant(input: &mut impl Iterator<Item = String>, seat_count: usize) -> Option<Variant> {
+    loop {
+        let answer = prompt(
+            input,
+            "[h]old'em, [o]maha, [5]-card, [6]-card or Omaha Hi-Lo [8], [s]tud, Stud Hi-Lo [s8], [r]azz, [d]raw or 2-7 [t]riple draw? ",
+        )?;
+        let variant = match answer.to_ascii_lowercase().as_str() {
+            "" | "h" | "holdem" | "hold'em" => Variant::Holdem,
+            "o" | "omaha" => Variant::Omaha,
+            "5" => Variant::FiveCardOmaha,
+            "6" => Variant::SixCardOmaha,
+            "8" => Variant::OmahaHiLo,
+            "s" | "stud" => Variant::SevenCardStud,
+            "s8" => Variant::SevenCardStudHiLo,
+            "r" | "razz" => Variant::Razz,
+            "d" | "draw" => Variant::FiveCardDraw,
+            "t" | "27" => Variant::DeuceToSevenTripleDraw,
+            _ => {
+                println!("Please enter h, o, 5, 6, 8, s, s8, r, d or t.");
+                continue;
+            }
+        };
+        if seat_count > variant.max_players() {
             println!(
-                "Player 2 wins with {} [{}]!",
-                player2_best_tier,
-                player2_best_hand_cards
-                    .iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ")
+                "{} can be dealt to at most {} players.",
+                variant,
+                variant.max_players()
             );
+            continue;
         }
-        std::cmp::Ordering::Equal => match player1_best_hand_cards.cmp(&player2_best_hand_cards) {
-            std::cmp::Ordering::Greater => {
-                println!(
-                    "Player 1 wins with {} [{}] (better kickers)!",
-                    player1_best_tier,
-                    player1_best_hand_cards
-                        .iter()
-                        .map(|c| c.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-            }
-            std::cmp::Ordering::Less => {
-                println!(
-                    "Player 2 wins with {} [{}] (better kickers)!",
-                    player2_best_tier,
-                    player2_best_hand_cards
-                        .iter()
-                        .map(|c| c.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-            }
-            std::cmp::Ordering::Equal => {
-                println!(
-                    "It's a complete tie! Both hands: [{}]",
-                    player1_best_hand_cards
-                        .iter()
-                        .map(|c| c.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
+        return Some(variant);
+    }
+}
+
+fn prompt_betting_structure(input: &mut impl Iterator<Item = String>) -> Option<BettingStructure> {
+    loop {
+        let answer = prompt(input, "[n]o-limit, [p]ot-limit or [f]ixed-limit? ")?;
+        match answer.to_ascii_lowercase().as_str() {
+            "" | "n" | "no-limit" => return Some(BettingStructure::NoLimit),
+            "p" | "pot-limit" => return Some(BettingStructure::PotLimit),
+            "f" | "fixed-limit" => {
+                return Some(BettingStructure::FixedLimit {
+                    small_bet: 50,
+                    big_bet: 100,
+                    max_raises: 3,
+                })
             }
-        },
+            _ => println!("Please enter n, p or f."),
+        }
+    }
+}
+
+fn prompt_action(input: &mut impl Iterator<Item = String>, game: &Game) -> Option<Action> {
+    loop {
+        let answer = prompt(input, "[f]old, chec[k], [c]all, [r]aise <amount>, [q]uit: ")?;
+        let mut words = answer.split_whitespace();
+        let action = match words
+            .next()
+            .map(|word| word.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("f") | Some("fold") => Some(Action::Fold),
+            Some("k") | Some("check") => Some(Action::Check),
+            Some("c") | Some("call") => Some(Action::Call(call_amount(game))),
+            Some("r") | Some("raise") => words
+                .next()
+                .and_then(|amount| amount.parse().ok())
+                .map(Action::Raise),
+            Some("q") | Some("quit") => return None,
+            _ => None,
+        };
+
+        match action {
+            Some(action) => return Some(action),
+            None => println!("Sorry, I didn't understand that."),
+        }
+    }
+}
+
+fn prompt_discards(input: &mut impl Iterator<Item = String>) -> Option<Vec<Card>> {
+    loop {
+        let answer = prompt(
+            input,
+            "Cards to discard (e.g. As 7d), enter to stand pat, [q]uit: ",
+        )?;
+        if answer.eq_ignore_ascii_case("q") || answer.eq_ignore_ascii_case("quit") {
+            return None;
+        }
+        match parse_cards(&answer) {
+            Ok(cards) => return Some(cards),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
+
+fn call_amount(game: &Game) -> u32 {
+    let chips = game
+        .current_player()
+        .and_then(|seat| game.player(seat))
+        .map_or(0, |player| player.chips);
+    game.to_call().min(chips)
+}
+
+fn bot_action(game: &Game, seat: usize) -> Action {
+    let player = game.player(seat).expect("The acting seat is occupied");
+    let to_call = game.to_call();
+
+    let strength = if let Some(lowball) = game.variant().lowball() {
+        lowball_strength(lowball, &[player.hand(), game.community_cards()].concat())
+    } else if player.hand().len() + game.community_cards().len() < 5 {
+        let paired = player.hand().iter().enumerate().any(|(i, card)| {
+            player.hand()[i + 1..]
+                .iter()
+                .any(|other| other.get_rank() == card.get_rank())
+        });
+        if paired {
+            HandCategory::OnePair
+        } else {
+            HandCategory::HighCard
+        }
+    } else {
+        game.variant()
+            .best_hand(player.hand(), game.community_cards())
+            .rank
+            .category()
+    };
+
+    if let Some((min, max)) = game.raise_limits() {
+        let raise = (game.pot_total() / 2).clamp(min, max);
+        if strength >= HandCategory::TwoPair && player.chips > to_call + raise {
+            return Action::Raise(raise);
+        }
+    }
+    if to_call == 0 {
+        return Action::Check;
+    }
+    if strength >= HandCategory::OnePair || to_call <= player.chips / 20 {
+        return Action::Call(to_call.min(player.chips));
+    }
+    Action::Fold
+}
+
+// Bots play a smooth low like a strong high hand. Straights and flushes
+// are ignored, which is close enough for a bot.
+fn lowball_strength(lowball: Lowball, cards: &[Card]) -> HandCategory {
+    let values: std::collections::BTreeSet<u8> = cards
+        .iter()
+        .map(|card| match lowball {
+            Lowball::DeuceToSeven => card.get_rank() as u8 + 2,
+            Lowball::AceToFive => card.get_rank().ace_low_value(),
+        })
+        .collect();
+    match values.iter().nth(4).or(values.last()) {
+        Some(&top) if top <= 8 => HandCategory::TwoPair,
+        Some(&top) if top <= 10 => HandCategory::OnePair,
+        _ => HandCategory::HighCard,
+    }
+}
+
+fn bot_discards(variant: Variant, hand: &[Card]) -> Vec<Card> {
+    if variant.lowball().is_some() {
+        let mut kept = Vec::new();
+        return hand
+            .iter()
+            .copied()
+            .filter(|card| {
+                let keep = card.get_rank() <= Rank::Eight && !kept.contains(&card.get_rank());
+                if keep {
+                    kept.push(card.get_rank());
+                }
+                !keep
+            })
+            .collect();
+    }
+
+    let category = Variant::FiveCardDraw.best_hand(hand, &[]).rank.category();
+    if category >= HandCategory::Straight {
+        return Vec::new();
+    }
+
+    let paired = |card: &Card| {
+        hand.iter()
+            .filter(|other| other.get_rank() == card.get_rank())
+            .count()
+            > 1
+    };
+    if hand.iter().any(paired) {
+        return hand.iter().copied().filter(|card| !paired(card)).collect();
     }
+
+    let mut by_rank = hand.to_vec();
+    by_rank.sort_by_key(|card| std::cmp::Reverse(card.get_rank()));
+    by_rank.split_off(2)
 }
 
-pub fn evaluate_best_hand(player_cards: &[Card], community_cards: &[Card]) -> BestHand {
-    let all_cards = [player_cards, community_cards].concat();
-    let mut best_hand: Option<BestHand> = None;
+fn describe(action: Action) -> String {
+    match action {
+        Action::Fold => "folds".to_string(),
+        Action::Check => "checks".to_string(),
+        Action::Call(amount) => format!("calls {}", amount),
+        Action::Raise(amount) => format!("raises {}", amount),
+    }
+}
 
-    for combo in all_cards.iter().cloned().combinations(5) {
-        let hand_array: [Card; 5] = combo.try_into().unwrap();
-        let tier = Tier::evaluate_hand(hand_array);
+fn join_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
-        let current = BestHand {
-            tier,
-            cards: hand_array,
+fn print_stacks(game: &Game) {
+    for (seat, player) in game.table().players() {
+        let button = if game.positions().map(|positions| positions.button) == Some(seat) {
+            " (button)"
+        } else {
+            ""
         };
+        println!("Seat {}: {}{}", seat + 1, player, button);
+    }
+}
 
-        if best_hand.as_ref().map_or(true, |best| current > *best) {
-            best_hand = Some(current);
+fn print_street(game: &Game) {
+    println!();
+    if game.variant().is_stud() {
+        println!("{} (pot {})", game.street(), game.pot_total());
+        for (_, player) in game
+            .table()
+            .players()
+            .filter(|(_, player)| player.is_playing)
+        {
+            println!("{} shows [{}]", player.name, join_cards(&player.up_cards()));
+        }
+        if !game.community_cards().is_empty() {
+            println!("Community card: [{}]", join_cards(game.community_cards()));
+        }
+    } else if game.community_cards().is_empty() {
+        println!("{} (pot {})", game.street(), game.pot_total());
+    } else {
+        println!(
+            "{}: [{}] (pot {})",
+            game.street(),
+            join_cards(game.community_cards()),
+            game.pot_total()
+        );
+    }
+}
+
+fn print_result(game: &Game) {
+    println!();
+    if !game.community_cards().is_empty() {
+        println!("Board: [{}]", join_cards(game.community_cards()));
+    }
+
+    for (seat, player) in game.table().players() {
+        if let Some(best) = game.best_hand(seat) {
+            println!(
+                "{} shows [{}]: {} with [{}]",
+                player.name,
+                join_cards(player.hand()),
+                best.rank,
+                join_cards(&best.cards)
+            );
+        }
+        if let Some(low) = game.best_lowball(seat) {
+            println!(
+                "{} shows [{}]: {}",
+                player.name,
+                join_cards(player.hand()),
+                low
+            );
+        }
+        if let Some(low) = game.best_low(seat) {
+            println!("{} has {}", player.name, low);
         }
     }
 
-    best_hand.expect("There should always be a best hand")
+    for (seat, amount) in game.payouts().iter() {
+        let player = game.player(seat).expect("Winners are seated");
+        println!("{} wins {} chips", player.name, amount);
+    }
 }
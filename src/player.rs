@@ -1,13 +1,35 @@
 use crate::card::{Card, Rank, Suit};
 use std::fmt;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChipError {
+    InvalidAmount,
+    InsufficientChips { needed: u32, available: u32 },
+    NoChips,
+    Overflow,
+}
+
+impl fmt::Display for ChipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChipError::InvalidAmount => write!(f, "The amount must be more than zero"),
+            ChipError::InsufficientChips { needed, available } => write!(
+                f,
+                "Needed {} chips but only {} are available",
+                needed, available
+            ),
+            ChipError::NoChips => write!(f, "The player has no chips left"),
+            ChipError::Overflow => write!(f, "Too many chips to count"),
+        }
+    }
+}
+
 pub struct Player<'a> {
     pub name: &'a str,
     pub chips: u32,
-    pub hand: Vec<Card>,
+    hand: Vec<Card>,
     pub is_playing: bool,
-    pub is_small_blind: bool,
-    pub is_big_blind: bool,
+    face_up: Vec<bool>,
 }
 
 impl<'a> Player<'a> {
@@ -17,37 +39,93 @@ impl<'a> Player<'a> {
             chips,
             hand: Vec::new(),
             is_playing: true,
-            is_small_blind: false,
-            is_big_blind: false,
+            face_up: Vec::new(),
         }
     }
 
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
     pub fn receive_card(&mut self, card: Card) {
         self.hand.push(card);
+        self.face_up.push(false);
+    }
+
+    pub fn receive_up_card(&mut self, card: Card) {
+        self.hand.push(card);
+        self.face_up.push(true);
     }
 
-    pub fn raise(&mut self, amount: u32) {
-        if self.chips >= amount {
-            self.chips -= amount;
+    pub fn discard(&mut self, card: Card) -> bool {
+        let Some(index) = self.hand.iter().position(|&held| held == card) else {
+            return false;
+        };
+        self.hand.remove(index);
+        self.face_up.remove(index);
+        true
+    }
+
+    pub fn is_face_up(&self, index: usize) -> Option<bool> {
+        self.face_up.get(index).copied()
+    }
+
+    pub fn up_cards(&self) -> Vec<Card> {
+        self.hand
+            .iter()
+            .zip(&self.face_up)
+            .filter(|&(_, &face_up)| face_up)
+            .map(|(&card, _)| card)
+            .collect()
+    }
+
+    pub fn bet(&mut self, amount: u32) -> Result<u32, ChipError> {
+        if amount == 0 {
+            return Err(ChipError::InvalidAmount);
+        }
+        if amount > self.chips {
+            return Err(ChipError::InsufficientChips {
+                needed: amount,
+                available: self.chips,
+            });
         }
+        self.chips -= amount;
+        Ok(amount)
     }
 
-    pub fn fold(&mut self) {
-        self.is_playing = false;
+    pub fn call(&mut self, amount: u32) -> Result<u32, ChipError> {
+        if amount == 0 {
+            return Err(ChipError::InvalidAmount);
+        }
+        if amount >= self.chips {
+            return self.all_in();
+        }
+        self.bet(amount)
     }
 
-    pub fn small_blind(&mut self) {
-        self.is_small_blind = true;
-        self.raise(25);
+    pub fn all_in(&mut self) -> Result<u32, ChipError> {
+        if self.chips == 0 {
+            return Err(ChipError::NoChips);
+        }
+        Ok(std::mem::take(&mut self.chips))
     }
 
-    pub fn big_blind(&mut self) {
-        self.is_big_blind = true;
-        self.raise(50);
+    pub fn collect(&mut self, amount: u32) -> Result<(), ChipError> {
+        self.chips = self.chips.checked_add(amount).ok_or(ChipError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn fold(&mut self) {
+        self.is_playing = false;
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        self.is_playing && self.chips == 0
     }
 
     pub fn clear_hand(&mut self) {
         self.hand.clear();
+        self.face_up.clear();
     }
 
     pub fn get_hand_value(&self) -> Vec<Rank> {
@@ -80,9 +158,7 @@ mod tests {
         assert_eq!(player.name, "Dusan");
         assert_eq!(player.chips, 100);
         assert!(player.is_playing);
-        assert!(!player.is_small_blind);
-        assert!(!player.is_big_blind);
-        assert_eq!(player.hand.len(), 0);
+        assert_eq!(player.hand().len(), 0);
     }
 
     #[test]
@@ -90,38 +166,82 @@ mod tests {
         let mut player = Player::new("Dusan", 100);
         let card = Card::new(Rank::Ace, Suit::Spades);
         player.receive_card(card);
-        assert_eq!(player.hand.len(), 1);
-        assert_eq!(player.hand[0], card);
+        assert_eq!(player.hand().len(), 1);
+        assert_eq!(player.hand()[0], card);
+    }
+
+    #[test]
+    fn test_up_and_down_cards() {
+        let mut player = Player::new("Dusan", 100);
+        let ace = Card::new(Rank::Ace, Suit::Spades);
+        let king = Card::new(Rank::King, Suit::Hearts);
+        player.receive_card(ace);
+        player.receive_up_card(king);
+
+        assert_eq!(player.is_face_up(0), Some(false));
+        assert_eq!(player.is_face_up(1), Some(true));
+        assert_eq!(player.is_face_up(2), None);
+        assert_eq!(player.up_cards(), vec![king]);
+
+        player.clear_hand();
+        player.receive_card(king);
+        assert!(player.up_cards().is_empty());
     }
 
     #[test]
-    fn test_raise() {
+    fn test_discard() {
         let mut player = Player::new("Dusan", 100);
-        player.raise(30);
+        let ace = Card::new(Rank::Ace, Suit::Spades);
+        let king = Card::new(Rank::King, Suit::Hearts);
+        player.receive_card(ace);
+        player.receive_up_card(king);
+
+        assert!(player.discard(ace));
+        assert!(!player.discard(ace));
+        assert_eq!(player.hand(), vec![king]);
+        assert_eq!(player.is_face_up(0), Some(true));
+    }
+
+    #[test]
+    fn test_bet() {
+        let mut player = Player::new("Dusan", 100);
+        assert_eq!(player.bet(30), Ok(30));
+        assert_eq!(player.chips, 70);
+        assert_eq!(player.bet(0), Err(ChipError::InvalidAmount));
+        assert_eq!(
+            player.bet(80),
+            Err(ChipError::InsufficientChips {
+                needed: 80,
+                available: 70
+            })
+        );
         assert_eq!(player.chips, 70);
     }
 
     #[test]
-    fn test_fold() {
+    fn test_short_call_goes_all_in() {
         let mut player = Player::new("Dusan", 100);
-        player.fold();
-        assert!(!player.is_playing);
+        assert_eq!(player.call(40), Ok(40));
+        assert_eq!(player.call(500), Ok(60));
+        assert!(player.is_all_in());
+        assert_eq!(player.call(10), Err(ChipError::NoChips));
+        assert_eq!(player.all_in(), Err(ChipError::NoChips));
     }
 
     #[test]
-    fn test_small_blind() {
+    fn test_collect() {
         let mut player = Player::new("Dusan", 100);
-        player.small_blind();
-        assert!(player.is_small_blind);
-        assert_eq!(player.chips, 75);
+        assert_eq!(player.collect(50), Ok(()));
+        assert_eq!(player.chips, 150);
+        assert_eq!(player.collect(u32::MAX), Err(ChipError::Overflow));
+        assert_eq!(player.chips, 150);
     }
 
     #[test]
-    fn test_big_blind() {
+    fn test_fold() {
         let mut player = Player::new("Dusan", 100);
-        player.big_blind();
-        assert!(player.is_big_blind);
-        assert_eq!(player.chips, 50);
+        player.fold();
+        assert!(!player.is_playing);
     }
 
     #[test]
@@ -131,9 +251,9 @@ mod tests {
         let card2 = Card::new(Rank::King, Suit::Hearts);
         player.receive_card(card1);
         player.receive_card(card2);
-        assert_eq!(player.hand.len(), 2);
+        assert_eq!(player.hand().len(), 2);
         player.clear_hand();
-        assert_eq!(player.hand.len(), 0);
+        assert_eq!(player.hand().len(), 0);
     }
 
     #[test]
@@ -151,7 +271,7 @@ mod tests {
     fn test_is_busted() {
         let mut player = Player::new("Dusan", 100);
         assert!(!player.is_busted());
-        player.raise(100);
+        player.all_in().unwrap();
         assert!(player.is_busted());
     }
 }
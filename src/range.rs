@@ -0,0 +1,549 @@
+use crate::card::{parse_cards, Card, CardSet, Rank, Suit};
+use crate::equity::{record_showdown, Equity, EquityError, MAX_PLAYERS, MIN_PLAYERS};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::str::FromStr;
+
+// Ranges that block each other almost completely would otherwise never deal.
+const MAX_DEAL_ATTEMPTS: usize = 10_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RangeError {
+    Empty,
+    InvalidHand(String),
+    InvalidWeight(String),
+    EmptyRange(usize),
+    NoCompatibleCombos,
+    Equity(EquityError),
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::Empty => write!(f, "A range needs at least one hand"),
+            RangeError::InvalidHand(text) => write!(f, "'{}' is not a hand or hand range", text),
+            RangeError::InvalidWeight(text) => {
+                write!(f, "'{}' is not a weight between 0 and 1", text)
+            }
+            RangeError::EmptyRange(index) => {
+                write!(f, "Every combo in range {} is blocked", index)
+            }
+            RangeError::NoCompatibleCombos => {
+                write!(f, "The ranges cannot be dealt without sharing cards")
+            }
+            RangeError::Equity(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<EquityError> for RangeError {
+    fn from(error: EquityError) -> Self {
+        RangeError::Equity(error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Combo {
+    pub cards: [Card; 2],
+    pub weight: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Range {
+    combos: Vec<Combo>,
+}
+
+impl Range {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn combos(&self) -> &[Combo] {
+        &self.combos
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|combo| combo.weight).sum()
+    }
+
+    pub fn contains(&self, cards: [Card; 2]) -> bool {
+        let cards = ordered(cards);
+        self.combos.iter().any(|combo| combo.cards == cards)
+    }
+
+    pub fn remove_blocked(&mut self, blockers: CardSet) -> usize {
+        let before = self.combos.len();
+        self.combos
+            .retain(|combo| combo.cards.iter().all(|&card| !blockers.contains(card)));
+        before - self.combos.len()
+    }
+
+    // A combo listed twice keeps the weight it was given last, so
+    // "AA, AsAh:0.5" plays every pair of aces but half of that one.
+    fn add(&mut self, cards: [Card; 2], weight: f64) {
+        let cards = ordered(cards);
+        match self.combos.iter_mut().find(|combo| combo.cards == cards) {
+            Some(combo) => combo.weight = weight,
+            None => self.combos.push(Combo { cards, weight }),
+        }
+    }
+
+    fn sample<R: Rng>(&self, cumulative: &[f64], rng: &mut R) -> [Card; 2] {
+        let total = cumulative.last().copied().unwrap_or(0.0);
+        let target = rng.gen_range(0.0..total);
+        let index = cumulative.partition_point(|&weight| weight <= target);
+        self.combos[index.min(self.combos.len() - 1)].cards
+    }
+
+    fn cumulative_weights(&self) -> Vec<f64> {
+        self.combos
+            .iter()
+            .scan(0.0, |total, combo| {
+                *total += combo.weight;
+                Some(*total)
+            })
+            .collect()
+    }
+}
+
+impl From<[Card; 2]> for Range {
+    fn from(cards: [Card; 2]) -> Self {
+        let mut range = Range::new();
+        range.add(cards, 1.0);
+        range
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::new();
+        for token in text
+            .split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+        {
+            let (hands, weight) = match token.split_once(':') {
+                Some((hands, weight)) => (hands.trim(), parse_weight(weight.trim())?),
+                None => (token, 1.0),
+            };
+            for cards in expand(hands)? {
+                range.add(cards, weight);
+            }
+        }
+
+        if range.is_empty() {
+            return Err(RangeError::Empty);
+        }
+        Ok(range)
+    }
+}
+
+fn ordered(cards: [Card; 2]) -> [Card; 2] {
+    if cards[0] < cards[1] {
+        [cards[1], cards[0]]
+    } else {
+        cards
+    }
+}
+
+fn parse_weight(text: &str) -> Result<f64, RangeError> {
+    text.parse::<f64>()
+        .ok()
+        .filter(|weight| weight.is_finite() && *weight > 0.0 && *weight <= 1.0)
+        .ok_or_else(|| RangeError::InvalidWeight(text.to_string()))
+}
+
+fn expand(text: &str) -> Result<Vec<[Card; 2]>, RangeError> {
+    let invalid = || RangeError::InvalidHand(text.to_string());
+
+    let classes = if let Some((first, last)) = text.split_once('-') {
+        let first = HandClass::parse(first.trim()).ok_or_else(invalid)?;
+        let last = HandClass::parse(last.trim()).ok_or_else(invalid)?;
+        first.span(last).ok_or_else(invalid)?
+    } else if let Some(base) = text.strip_suffix('+') {
+        HandClass::parse(base).ok_or_else(invalid)?.and_better()
+    } else if let Ok(cards) = parse_cards(text) {
+        return match cards[..] {
+            [first, second] => Ok(vec![[first, second]]),
+            _ => Err(invalid()),
+        };
+    } else {
+        vec![HandClass::parse(text).ok_or_else(invalid)?]
+    };
+
+    Ok(classes.iter().flat_map(HandClass::combos).collect())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct HandClass {
+    high: Rank,
+    low: Rank,
+    suitedness: Suitedness,
+}
+
+impl HandClass {
+    fn parse(text: &str) -> Option<HandClass> {
+        let mut chars = text.chars();
+        let first: Rank = chars.next()?.to_string().parse().ok()?;
+        let second: Rank = chars.next()?.to_string().parse().ok()?;
+        let suitedness = match chars.next() {
+            None => Suitedness::Any,
+            Some('s' | 'S') => Suitedness::Suited,
+            Some('o' | 'O') => Suitedness::Offsuit,
+            Some(_) => return None,
+        };
+        if chars.next().is_some() || (first == second && suitedness != Suitedness::Any) {
+            return None;
+        }
+
+        Some(HandClass {
+            high: first.max(second),
+            low: first.min(second),
+            suitedness,
+        })
+    }
+
+    fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    fn with_low(&self, low: Rank) -> HandClass {
+        let high = if self.is_pair() { low } else { self.high };
+        HandClass { high, low, ..*self }
+    }
+
+    // "QQ+" climbs to aces, "KTo+" climbs the kicker up to KQo.
+    fn and_better(&self) -> Vec<HandClass> {
+        let top = if self.is_pair() {
+            Rank::Ace as usize
+        } else {
+            self.high as usize - 1
+        };
+        Rank::ALL[self.low as usize..=top]
+            .iter()
+            .map(|&low| self.with_low(low))
+            .collect()
+    }
+
+    fn span(&self, other: HandClass) -> Option<Vec<HandClass>> {
+        let same_shape = if self.is_pair() {
+            other.is_pair()
+        } else {
+            self.high == other.high && self.suitedness == other.suitedness
+        };
+        if !same_shape {
+            return None;
+        }
+
+        let (from, to) = (self.low.min(other.low), self.low.max(other.low));
+        Some(
+            Rank::ALL[from as usize..=to as usize]
+                .iter()
+                .map(|&low| self.with_low(low))
+                .collect(),
+        )
+    }
+
+    fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::new();
+        for (i, &first) in Suit::ALL.iter().enumerate() {
+            for (j, &second) in Suit::ALL.iter().enumerate() {
+                let keep = if self.is_pair() {
+                    i < j
+                } else {
+                    match self.suitedness {
+                        Suitedness::Suited => i == j,
+                        Suitedness::Offsuit => i != j,
+                        Suitedness::Any => true,
+                    }
+                };
+                if keep {
+                    combos.push([Card::new(self.high, first), Card::new(self.low, second)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+pub struct RangeCalculator {
+    ranges: Vec<Range>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+    iterations: u64,
+    seed: u64,
+}
+
+impl RangeCalculator {
+    pub fn new(ranges: Vec<Range>) -> Self {
+        Self {
+            ranges,
+            board: Vec::new(),
+            dead: Vec::new(),
+            iterations: 10_000,
+            seed: rand::random(),
+        }
+    }
+
+    pub fn set_board(&mut self, board: Vec<Card>) {
+        self.board = board;
+    }
+
+    pub fn set_dead(&mut self, dead: Vec<Card>) {
+        self.dead = dead;
+    }
+
+    pub fn set_iterations(&mut self, iterations: u64) {
+        self.iterations = iterations;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub fn monte_carlo(&self) -> Result<Vec<Equity>, RangeError> {
+        let known = self.validate()?;
+        if self.iterations == 0 {
+            return Err(EquityError::NoIterations.into());
+        }
+
+        let mut ranges = self.ranges.clone();
+        for (index, range) in ranges.iter_mut().enumerate() {
+            range.remove_blocked(known);
+            if range.is_empty() {
+                return Err(RangeError::EmptyRange(index));
+            }
+        }
+        let cumulative: Vec<Vec<f64>> = ranges.iter().map(Range::cumulative_weights).collect();
+
+        let board: CardSet = self.board.iter().copied().collect();
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut hands = Vec::with_capacity(ranges.len());
+        let mut results = vec![Equity::default(); ranges.len()];
+
+        for _ in 0..self.iterations {
+            let mut used = deal_hands(&ranges, &cumulative, known, &mut hands, &mut rng)?;
+            let mut full_board = board;
+            while full_board.len() < 5 {
+                let card = Card::from_index(rng.gen_range(0..52)).expect("Indexes below 52");
+                if used.insert(card) {
+                    full_board.insert(card);
+                }
+            }
+            record_showdown(&hands, full_board, &mut results);
+        }
+
+        Ok(results)
+    }
+
+    fn validate(&self) -> Result<CardSet, EquityError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&self.ranges.len()) {
+            return Err(EquityError::InvalidPlayerCount(self.ranges.len()));
+        }
+        if self.board.len() > 5 {
+            return Err(EquityError::BoardTooLong(self.board.len()));
+        }
+
+        let mut known = CardSet::new();
+        for &card in self.board.iter().chain(&self.dead) {
+            if !known.insert(card) {
+                return Err(EquityError::DuplicateCard(card));
+            }
+        }
+
+        // Two cards go to each range before the board is filled in.
+        let needed = 5 - self.board.len();
+        let available = 52usize.saturating_sub(known.len() + 2 * self.ranges.len());
+        if available < needed {
+            return Err(EquityError::NotEnoughCards { needed, available });
+        }
+        Ok(known)
+    }
+}
+
+// Each range is sampled on its own and the whole deal is redrawn when two
+// hands collide, which keeps every compatible deal equally likely.
+fn deal_hands<R: Rng>(
+    ranges: &[Range],
+    cumulative: &[Vec<f64>],
+    known: CardSet,
+    hands: &mut Vec<CardSet>,
+    rng: &mut R,
+) -> Result<CardSet, RangeError> {
+    'attempt: for _ in 0..MAX_DEAL_ATTEMPTS {
+        let mut used = known;
+        hands.clear();
+        for (range, cumulative) in ranges.iter().zip(cumulative) {
+            let hand: CardSet = range.sample(cumulative, rng).into_iter().collect();
+            if !used.is_disjoint(hand) {
+                continue 'attempt;
+            }
+            used = used.union(hand);
+            hands.push(hand);
+        }
+        return Ok(used);
+    }
+    Err(RangeError::NoCompatibleCombos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(text: &str) -> [Card; 2] {
+        let cards = parse_cards(text).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    fn range(text: &str) -> Range {
+        text.parse().unwrap()
+    }
+
+    fn calculator(ranges: &[&str], board: &str) -> RangeCalculator {
+        let mut calculator = RangeCalculator::new(ranges.iter().map(|text| range(text)).collect());
+        calculator.set_board(parse_cards(board).unwrap());
+        calculator.set_iterations(20_000);
+        calculator.set_seed(7);
+        calculator
+    }
+
+    #[test]
+    fn test_parses_standard_notation() {
+        assert_eq!(range("QQ+").len(), 18);
+        assert_eq!(range("AKs").len(), 4);
+        assert_eq!(range("A2s-A5s").len(), 16);
+        assert_eq!(range("KTo+").len(), 36);
+        assert_eq!(range("76s").len(), 4);
+        assert_eq!(range("QQ+, AKs, A2s-A5s, KTo+, 76s").len(), 78);
+
+        assert_eq!(range("AK").len(), 16);
+        assert_eq!(range("55-22").len(), 24);
+        assert_eq!(range("A5s-A2s"), range("A2s-A5s"));
+        assert_eq!(range("ka"), range("AK"));
+
+        let kings = range("KTo+");
+        assert!(kings.contains(cards("Kh Qs")));
+        assert!(!kings.contains(cards("Ks Qs")));
+        assert!(!kings.contains(cards("Ks 9h")));
+    }
+
+    #[test]
+    fn test_weights_and_specific_combos() {
+        let aces = range("AA, AsAh:0.5");
+        assert_eq!(aces.len(), 6);
+        assert!((aces.total_weight() - 5.5).abs() < 1e-12);
+        assert!(aces.contains(cards("Ah As")));
+
+        let mixed = range("JJ:0.25, AQo");
+        assert!((mixed.total_weight() - 13.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_removes_blocked_combos() {
+        let mut broadway = range("AA, AKs");
+        assert_eq!(broadway.remove_blocked("As Kh".parse().unwrap()), 5);
+        assert_eq!(broadway.len(), 5);
+        let ace: Card = "As".parse().unwrap();
+        assert!(broadway
+            .combos()
+            .iter()
+            .all(|combo| !combo.cards.contains(&ace)));
+    }
+
+    #[test]
+    fn test_rejects_bad_ranges() {
+        assert_eq!("".parse::<Range>(), Err(RangeError::Empty));
+        assert_eq!(
+            "AKx".parse::<Range>(),
+            Err(RangeError::InvalidHand("AKx".to_string()))
+        );
+        assert_eq!(
+            "AAs".parse::<Range>(),
+            Err(RangeError::InvalidHand("AAs".to_string()))
+        );
+        assert_eq!(
+            "A2s-K5s".parse::<Range>(),
+            Err(RangeError::InvalidHand("A2s-K5s".to_string()))
+        );
+        assert_eq!(
+            "AKs:2".parse::<Range>(),
+            Err(RangeError::InvalidWeight("2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_hand_against_range() {
+        let hand = cards("Ah Ad");
+        let mut calculator = RangeCalculator::new(vec![Range::from(hand), range("KK")]);
+        calculator.set_seed(7);
+        calculator.set_iterations(20_000);
+
+        let results = calculator.monte_carlo().unwrap();
+        assert!((results[0].equity() - 0.82).abs() < 0.02);
+        assert_eq!(results[0].trials(), 20_000);
+    }
+
+    #[test]
+    fn test_range_against_range() {
+        let results = calculator(&["QQ+, AKs", "22-66, KQs, JTs"], "")
+            .monte_carlo()
+            .unwrap();
+        assert!(results[0].equity() > 0.6);
+        assert!((results[0].equity() + results[1].equity() - 1.0).abs() < 1e-9);
+
+        let first = calculator(&["QQ+", "AK", "76s"], "Ah 7c 2d").monte_carlo();
+        let second = calculator(&["QQ+", "AK", "76s"], "Ah 7c 2d").monte_carlo();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_rejects_ranges_that_cannot_be_dealt() {
+        assert_eq!(
+            calculator(&["AsAh", "KK"], "As 7c 2d").monte_carlo(),
+            Err(RangeError::EmptyRange(0))
+        );
+        assert_eq!(
+            calculator(&["AsAh", "AsAh"], "").monte_carlo(),
+            Err(RangeError::NoCompatibleCombos)
+        );
+        assert_eq!(
+            calculator(&["AA"], "").monte_carlo(),
+            Err(RangeError::Equity(EquityError::InvalidPlayerCount(1)))
+        );
+
+        let held = parse_cards("As Ah Ks Kh").unwrap();
+        let mut calculator = calculator(&["AsAh", "KsKh"], "");
+        calculator.set_dead(
+            CardSet::FULL
+                .into_iter()
+                .filter(|card| !held.contains(card))
+                .take(45)
+                .collect(),
+        );
+        assert_eq!(
+            calculator.monte_carlo(),
+            Err(RangeError::Equity(EquityError::NotEnoughCards {
+                needed: 5,
+                available: 3
+            }))
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::player::Player;
+use std::fmt;
+
+pub const MIN_SEATS: usize = 2;
+pub const MAX_SEATS: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableError {
+    InvalidSeatCount(usize),
+    NoSuchSeat(usize),
+    SeatTaken(usize),
+    SeatEmpty(usize),
+    TableFull,
+    NotEnoughPlayers,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::InvalidSeatCount(count) => write!(
+                f,
+                "A table needs between {} and {} seats, not {}",
+                MIN_SEATS, MAX_SEATS, count
+            ),
+            TableError::NoSuchSeat(seat) => write!(f, "Seat {} does not exist", seat),
+            TableError::SeatTaken(seat) => write!(f, "Seat {} is already taken", seat),
+            TableError::SeatEmpty(seat) => write!(f, "Seat {} is empty", seat),
+            TableError::TableFull => write!(f, "Every seat is taken"),
+            TableError::NotEnoughPlayers => write!(f, "At least two players with chips are needed"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Positions {
+    pub button: usize,
+    pub small_blind: Option<usize>,
+    pub big_blind: usize,
+}
+
+pub struct Table<'a> {
+    seats: Vec<Option<Player<'a>>>,
+    button: Option<usize>,
+    small_blind_seat: Option<usize>,
+    big_blind_seat: Option<usize>,
+    positions: Option<Positions>,
+}
+
+impl<'a> Table<'a> {
+    pub fn new(seat_count: usize) -> Result<Self, TableError> {
+        if !(MIN_SEATS..=MAX_SEATS).contains(&seat_count) {
+            return Err(TableError::InvalidSeatCount(seat_count));
+        }
+
+        Ok(Self {
+            seats: (0..seat_count).map(|_| None).collect(),
+            button: None,
+            small_blind_seat: None,
+            big_blind_seat: None,
+            positions: None,
+        })
+    }
+
+    pub fn with_players(players: Vec<Player<'a>>) -> Result<Self, TableError> {
+        let mut table = Self::new(players.len())?;
+        for (seat, player) in players.into_iter().enumerate() {
+            table.sit(seat, player)?;
+        }
+        Ok(table)
+    }
+
+    pub fn seat_count(&self) -> usize {
+        self.seats.len()
+    }
+
+    pub fn sit(&mut self, seat: usize, player: Player<'a>) -> Result<(), TableError> {
+        match self.seats.get_mut(seat) {
+            None => Err(TableError::NoSuchSeat(seat)),
+            Some(Some(_)) => Err(TableError::SeatTaken(seat)),
+            Some(slot) => {
+                *slot = Some(player);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn sit_anywhere(&mut self, player: Player<'a>) -> Result<usize, TableError> {
+        let seat = self
+            .seats
+            .iter()
+            .position(|slot| slot.is_none())
+            .ok_or(TableError::TableFull)?;
+        self.seats[seat] = Some(player);
+        Ok(seat)
+    }
+
+    pub fn leave(&mut self, seat: usize) -> Result<Player<'a>, TableError> {
+        self.seats
+            .get_mut(seat)
+            .ok_or(TableError::NoSuchSeat(seat))?
+            .take()
+            .ok_or(TableError::SeatEmpty(seat))
+    }
+
+    pub fn player(&self, seat: usize) -> Option<&Player<'a>> {
+        self.seats.get(seat).and_then(|slot| slot.as_ref())
+    }
+
+    pub fn player_mut(&mut self, seat: usize) -> Option<&mut Player<'a>> {
+        self.seats.get_mut(seat).and_then(|slot| slot.as_mut())
+    }
+
+    pub fn occupied_seats(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.seats.len()).filter(|&seat| self.seats[seat].is_some())
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = (usize, &Player<'a>)> {
+        self.seats
+            .iter()
+            .enumerate()
+            .filter_map(|(seat, slot)| slot.as_ref().map(|player| (seat, player)))
+    }
+
+    pub fn active_count(&self) -> usize {
+        (0..self.seats.len())
+            .filter(|&seat| self.is_active(seat))
+            .count()
+    }
+
+    pub fn total_chips(&self) -> u64 {
+        self.players()
+            .map(|(_, player)| u64::from(player.chips))
+            .sum()
+    }
+
+    pub fn button(&self) -> Option<usize> {
+        self.button
+    }
+
+    pub fn positions(&self) -> Option<Positions> {
+        self.positions
+    }
+
+    pub fn set_button(&mut self, seat: usize) -> Result<(), TableError> {
+        if seat >= self.seats.len() {
+            return Err(TableError::NoSuchSeat(seat));
+        }
+        self.button = Some(seat);
+        self.small_blind_seat = None;
+        self.big_blind_seat = None;
+        self.positions = None;
+        Ok(())
+    }
+
+    pub fn next_active(&self, from: usize) -> Option<usize> {
+        let count = self.seats.len();
+        (1..=count)
+            .map(|offset| (from + offset) % count)
+            .find(|&seat| self.is_active(seat))
+    }
+
+    pub fn advance_button(&mut self) -> Result<Positions, TableError> {
+        if self.active_count() < 2 {
+            return Err(TableError::NotEnoughPlayers);
+        }
+
+        let positions = match (self.small_blind_seat, self.big_blind_seat) {
+            (Some(small_blind_seat), Some(big_blind_seat)) => {
+                self.rotate_blinds(small_blind_seat, big_blind_seat)
+            }
+            _ => self.first_positions(),
+        };
+
+        self.button = Some(positions.button);
+        self.positions = Some(positions);
+        Ok(positions)
+    }
+
+    fn is_active(&self, seat: usize) -> bool {
+        self.player(seat).is_some_and(|player| !player.is_busted())
+    }
+
+    fn first_positions(&mut self) -> Positions {
+        let start = self.button.unwrap_or(self.seats.len() - 1);
+        let button = if self.is_active(start) && self.button.is_some() {
+            start
+        } else {
+            self.next_active(start).expect("two active players")
+        };
+
+        let (small_blind, big_blind) = if self.active_count() == 2 {
+            (
+                button,
+                self.next_active(button).expect("two active players"),
+            )
+        } else {
+            let small_blind = self.next_active(button).expect("two active players");
+            (
+                small_blind,
+                self.next_active(small_blind).expect("two active players"),
+            )
+        };
+
+        self.small_blind_seat = Some(small_blind);
+        self.big_blind_seat = Some(big_blind);
+        Positions {
+            button,
+            small_blind: Some(small_blind),
+            big_blind,
+        }
+    }
+
+    fn rotate_blinds(&mut self, small_blind_seat: usize, big_blind_seat: usize) -> Positions {
+        let big_blind = self
+            .next_active(big_blind_seat)
+            .expect("two active players");
+
+        if self.active_count() == 2 {
+            let button = self.next_active(big_blind).expect("two active players");
+            self.small_blind_seat = Some(button);
+            self.big_blind_seat = Some(big_blind);
+            return Positions {
+                button,
+                small_blind: Some(button),
+                big_blind,
+            };
+        }
+
+        let small_blind = Some(big_blind_seat).filter(|&seat| self.is_active(seat));
+        self.small_blind_seat = Some(big_blind_seat);
+        self.big_blind_seat = Some(big_blind);
+        Positions {
+            button: small_blind_seat,
+            small_blind,
+            big_blind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_table(count: usize) -> Table<'static> {
+        let names = ["Dusan", "Ana", "Marko", "Jelena", "Nikola", "Ivana"];
+        let players = names[..count]
+            .iter()
+            .map(|name| Player::new(name, 1000))
+            .collect();
+        Table::with_players(players).unwrap()
+    }
+
+    #[test]
+    fn test_seat_count_limits() {
+        assert_eq!(Table::new(1).err(), Some(TableError::InvalidSeatCount(1)));
+        assert_eq!(Table::new(11).err(), Some(TableError::InvalidSeatCount(11)));
+        assert_eq!(Table::new(9).unwrap().seat_count(), 9);
+    }
+
+    #[test]
+    fn test_sit_and_leave() {
+        let mut table = Table::new(3).unwrap();
+        table.sit(1, Player::new("Dusan", 100)).unwrap();
+
+        assert_eq!(
+            table.sit(1, Player::new("Ana", 100)),
+            Err(TableError::SeatTaken(1))
+        );
+        assert_eq!(
+            table.sit(3, Player::new("Ana", 100)),
+            Err(TableError::NoSuchSeat(3))
+        );
+        assert_eq!(table.sit_anywhere(Player::new("Ana", 100)), Ok(0));
+        assert_eq!(table.sit_anywhere(Player::new("Marko", 100)), Ok(2));
+        assert_eq!(
+            table.sit_anywhere(Player::new("Jelena", 100)),
+            Err(TableError::TableFull)
+        );
+
+        assert_eq!(table.leave(1).unwrap().name, "Dusan");
+        assert_eq!(table.leave(1).err(), Some(TableError::SeatEmpty(1)));
+        assert_eq!(table.occupied_seats().collect::<Vec<_>>(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_button_rotates_with_blinds() {
+        let mut table = full_table(4);
+        table.set_button(0).unwrap();
+
+        let first = table.advance_button().unwrap();
+        assert_eq!(
+            first,
+            Positions {
+                button: 0,
+                small_blind: Some(1),
+                big_blind: 2
+            }
+        );
+
+        let second = table.advance_button().unwrap();
+        assert_eq!(
+            second,
+            Positions {
+                button: 1,
+                small_blind: Some(2),
+                big_blind: 3
+            }
+        );
+        assert_eq!(table.button(), Some(1));
+    }
+
+    #[test]
+    fn test_heads_up_button_posts_small_blind() {
+        let mut table = full_table(2);
+        table.set_button(0).unwrap();
+
+        let first = table.advance_button().unwrap();
+        assert_eq!(first.small_blind, Some(0));
+        assert_eq!(first.big_blind, 1);
+
+        let second = table.advance_button().unwrap();
+        assert_eq!(second.button, 1);
+        assert_eq!(second.small_blind, Some(1));
+        assert_eq!(second.big_blind, 0);
+    }
+
+    #[test]
+    fn test_dead_small_blind_when_big_blind_leaves() {
+        let mut table = full_table(5);
+        table.set_button(0).unwrap();
+        table.advance_button().unwrap();
+
+        table.leave(2).unwrap();
+        let positions = table.advance_button().unwrap();
+        assert_eq!(
+            positions,
+            Positions {
+                button: 1,
+                small_blind: None,
+                big_blind: 3
+            }
+        );
+
+        let positions = table.advance_button().unwrap();
+        assert_eq!(
+            positions,
+            Positions {
+                button: 2,
+                small_blind: Some(3),
+                big_blind: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_big_blind_never_skipped_when_going_heads_up() {
+        let mut table = full_table(3);
+        table.set_button(0).unwrap();
+        table.advance_button().unwrap();
+
+        table.player_mut(0).unwrap().chips = 0;
+        let positions = table.advance_button().unwrap();
+        assert_eq!(positions.big_blind, 1);
+        assert_eq!(positions.button, 2);
+        assert_eq!(positions.small_blind, Some(2));
+    }
+
+    #[test]
+    fn test_needs_two_active_players() {
+        let mut table = Table::new(4).unwrap();
+        table.sit(0, Player::new("Dusan", 100)).unwrap();
+        table.sit(2, Player::new("Ana", 0)).unwrap();
+        assert_eq!(table.advance_button(), Err(TableError::NotEnoughPlayers));
+    }
+}
@@ -0,0 +1,502 @@
+use crate::betting::{Action, BettingStructure, BlindStructure};
+use crate::card::Card;
+use crate::game::{Game, GameError, Variant};
+use crate::table::Table;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelClock {
+    Hands,
+    Minutes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlindLevel {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+    pub length: u32,
+}
+
+impl BlindLevel {
+    pub fn blinds(&self) -> BlindStructure {
+        BlindStructure {
+            ante: self.ante,
+            ..BlindStructure::new(self.small_blind, self.big_blind)
+        }
+    }
+}
+
+impl fmt::Display for BlindLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.small_blind, self.big_blind)?;
+        if self.ante > 0 {
+            write!(f, " ante {}", self.ante)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    Empty,
+    InvalidClock(String),
+    InvalidLevel(usize),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::Empty => write!(f, "The schedule has no levels"),
+            ScheduleError::InvalidClock(clock) => {
+                write!(f, "Expected 'hands' or 'minutes', not '{}'", clock)
+            }
+            ScheduleError::InvalidLevel(line) => write!(f, "Line {} is not a valid level", line),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlindSchedule {
+    clock: LevelClock,
+    levels: Vec<BlindLevel>,
+}
+
+impl BlindSchedule {
+    pub fn new(clock: LevelClock, levels: Vec<BlindLevel>) -> Result<Self, ScheduleError> {
+        if levels.is_empty() {
+            return Err(ScheduleError::Empty);
+        }
+        Ok(Self { clock, levels })
+    }
+
+    pub fn clock(&self) -> LevelClock {
+        self.clock
+    }
+
+    pub fn levels(&self) -> &[BlindLevel] {
+        &self.levels
+    }
+
+    fn parse_level(line: &str) -> Option<BlindLevel> {
+        let (blinds, length) = line.split_once(char::is_whitespace)?;
+        let amounts = blinds
+            .split('/')
+            .map(|amount| amount.parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let (small_blind, big_blind, ante) = match amounts[..] {
+            [small_blind, big_blind] => (small_blind, big_blind, 0),
+            [small_blind, big_blind, ante] => (small_blind, big_blind, ante),
+            _ => return None,
+        };
+
+        let length = length.trim().parse().ok().filter(|&length| length > 0)?;
+        Some(BlindLevel {
+            small_blind,
+            big_blind,
+            ante,
+            length,
+        })
+    }
+}
+
+// The first line names the clock ("hands" or "minutes"), then each line is
+// "small/big[/ante] length". Blank lines and lines starting with '#' are skipped.
+impl FromStr for BlindSchedule {
+    type Err = ScheduleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let clock = match lines.next() {
+            None => return Err(ScheduleError::Empty),
+            Some((_, clock)) if clock.eq_ignore_ascii_case("hands") => LevelClock::Hands,
+            Some((_, clock)) if clock.eq_ignore_ascii_case("minutes") => LevelClock::Minutes,
+            Some((_, clock)) => return Err(ScheduleError::InvalidClock(clock.to_string())),
+        };
+
+        let levels = lines
+            .map(|(number, line)| {
+                Self::parse_level(line).ok_or(ScheduleError::InvalidLevel(number))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(clock, levels)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Finish<'a> {
+    pub name: &'a str,
+    pub position: usize,
+    pub hand: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TournamentError {
+    TournamentOver,
+    Game(GameError),
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TournamentError::TournamentOver => write!(f, "The tournament is over"),
+            TournamentError::Game(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<GameError> for TournamentError {
+    fn from(error: GameError) -> Self {
+        TournamentError::Game(error)
+    }
+}
+
+pub struct Tournament<'a> {
+    game: Game<'a>,
+    schedule: BlindSchedule,
+    level: usize,
+    hands_in_level: u32,
+    time_in_level: Duration,
+    hands_played: u32,
+    hand_pending: bool,
+    starting_stacks: Vec<u32>,
+    finishes: Vec<Finish<'a>>,
+}
+
+impl<'a> Tournament<'a> {
+    pub fn new(table: Table<'a>, schedule: BlindSchedule) -> Self {
+        let mut game = Game::new(table);
+        game.set_blinds(schedule.levels[0].blinds());
+        Self {
+            game,
+            schedule,
+            level: 0,
+            hands_in_level: 0,
+            time_in_level: Duration::ZERO,
+            hands_played: 0,
+            hand_pending: false,
+            starting_stacks: Vec::new(),
+            finishes: Vec::new(),
+        }
+    }
+
+    pub fn game(&self) -> &Game<'a> {
+        &self.game
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.game.set_seed(seed);
+    }
+
+    pub fn set_betting_structure(&mut self, structure: BettingStructure) {
+        self.game.set_betting_structure(structure);
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) -> Result<(), TournamentError> {
+        Ok(self.game.set_variant(variant)?)
+    }
+
+    pub fn level(&self) -> BlindLevel {
+        self.schedule.levels[self.level]
+    }
+
+    pub fn level_number(&self) -> usize {
+        self.level + 1
+    }
+
+    pub fn hands_played(&self) -> u32 {
+        self.hands_played
+    }
+
+    pub fn advance_clock(&mut self, elapsed: Duration) {
+        self.time_in_level += elapsed;
+    }
+
+    pub fn start_hand(&mut self) -> Result<(), TournamentError> {
+        if self.hand_pending {
+            self.finish_hand()?;
+        }
+        if self.is_over() {
+            return Err(TournamentError::TournamentOver);
+        }
+
+        self.update_level();
+        self.starting_stacks = (0..self.game.table().seat_count())
+            .map(|seat| self.game.player(seat).map_or(0, |player| player.chips))
+            .collect();
+        self.game.start_hand()?;
+        self.hand_pending = true;
+        Ok(())
+    }
+
+    pub fn act(&mut self, action: Action) -> Result<(), TournamentError> {
+        self.game.act(action)?;
+        Ok(())
+    }
+
+    pub fn discard(&mut self, cards: &[Card]) -> Result<Vec<Card>, TournamentError> {
+        Ok(self.game.discard(cards)?)
+    }
+
+    pub fn finish_hand(&mut self) -> Result<Vec<Finish<'a>>, TournamentError> {
+        if !self.hand_pending {
+            return Ok(Vec::new());
+        }
+        if !self.game.is_hand_over() {
+            return Err(TournamentError::Game(GameError::HandInProgress));
+        }
+
+        self.hand_pending = false;
+        self.hands_played += 1;
+        self.hands_in_level += 1;
+
+        let table = self.game.table_mut()?;
+        let busted: Vec<usize> = table
+            .players()
+            .filter(|(_, player)| player.is_busted())
+            .map(|(seat, _)| seat)
+            .collect();
+        let remaining = table.occupied_seats().count() - busted.len();
+
+        let mut finishes = Vec::with_capacity(busted.len() + 1);
+        for &seat in &busted {
+            let stack = self.starting_stacks.get(seat).copied().unwrap_or(0);
+            let bigger = busted
+                .iter()
+                .filter(|&&other| self.starting_stacks.get(other).copied().unwrap_or(0) > stack)
+                .count();
+            let player = table.leave(seat).map_err(GameError::from)?;
+            finishes.push(Finish {
+                name: player.name,
+                position: remaining + bigger + 1,
+                hand: self.hands_played,
+            });
+        }
+
+        if remaining == 1 {
+            if let Some((_, winner)) = table.players().next() {
+                finishes.push(Finish {
+                    name: winner.name,
+                    position: 1,
+                    hand: self.hands_played,
+                });
+            }
+        }
+
+        finishes.sort_by_key(|finish| finish.position);
+        self.finishes.extend(&finishes);
+        Ok(finishes)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game.table().active_count() < 2
+    }
+
+    pub fn results(&self) -> Vec<Finish<'a>> {
+        let mut results = self.finishes.clone();
+        results.sort_by_key(|finish| finish.position);
+        results
+    }
+
+    fn update_level(&mut self) {
+        while self.level + 1 < self.schedule.levels.len() {
+            let length = self.level().length;
+            match self.schedule.clock {
+                LevelClock::Hands if self.hands_in_level >= length => {
+                    self.hands_in_level -= length;
+                }
+                LevelClock::Minutes
+                    if self.time_in_level >= Duration::from_secs(u64::from(length) * 60) =>
+                {
+                    self.time_in_level -= Duration::from_secs(u64::from(length) * 60);
+                }
+                _ => break,
+            }
+            self.level += 1;
+        }
+        self.game.set_blinds(self.level().blinds());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    const SCHEDULE: &str = "
+        # Turbo
+        hands
+        25/50 2
+        50/100 2
+        100/200/25 2
+    ";
+
+    fn new_tournament(stacks: &[u32], schedule: &str) -> Tournament<'static> {
+        let names = ["Dusan", "Ana", "Marko", "Jelena"];
+        let players = stacks
+            .iter()
+            .zip(names)
+            .map(|(&chips, name)| Player::new(name, chips))
+            .collect();
+        let mut table = Table::with_players(players).unwrap();
+        table.set_button(0).unwrap();
+        Tournament::new(table, schedule.parse().unwrap())
+    }
+
+    fn fold_around(tournament: &mut Tournament) {
+        while !tournament.game().is_hand_over() {
+            tournament.act(Action::Fold).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let schedule: BlindSchedule = SCHEDULE.parse().unwrap();
+        assert_eq!(schedule.clock(), LevelClock::Hands);
+        assert_eq!(schedule.levels().len(), 3);
+        assert_eq!(
+            schedule.levels()[2],
+            BlindLevel {
+                small_blind: 100,
+                big_blind: 200,
+                ante: 25,
+                length: 2
+            }
+        );
+        assert_eq!(schedule.levels()[2].to_string(), "100/200 ante 25");
+    }
+
+    #[test]
+    fn test_parse_schedule_errors() {
+        assert_eq!("".parse::<BlindSchedule>(), Err(ScheduleError::Empty));
+        assert_eq!("hands".parse::<BlindSchedule>(), Err(ScheduleError::Empty));
+        assert_eq!(
+            "laps\n25/50 10".parse::<BlindSchedule>(),
+            Err(ScheduleError::InvalidClock("laps".to_string()))
+        );
+        assert_eq!(
+            "hands\n25/50 10\n50-100 10".parse::<BlindSchedule>(),
+            Err(ScheduleError::InvalidLevel(3))
+        );
+        assert_eq!(
+            "minutes\n25/50 0".parse::<BlindSchedule>(),
+            Err(ScheduleError::InvalidLevel(2))
+        );
+    }
+
+    #[test]
+    fn test_levels_go_up_by_hand_count() {
+        let mut tournament = new_tournament(&[5000, 5000, 5000], SCHEDULE);
+
+        let mut levels = Vec::new();
+        for _ in 0..7 {
+            tournament.start_hand().unwrap();
+            levels.push(tournament.level_number());
+            fold_around(&mut tournament);
+        }
+
+        assert_eq!(levels, vec![1, 1, 2, 2, 3, 3, 3]);
+        assert_eq!(tournament.game().blinds().ante, 25);
+        assert_eq!(tournament.hands_played(), 6);
+    }
+
+    #[test]
+    fn test_levels_go_up_by_clock() {
+        let schedule = "minutes\n25/50 15\n50/100 15\n100/200 15";
+        let mut tournament = new_tournament(&[5000, 5000], schedule);
+
+        tournament.start_hand().unwrap();
+        fold_around(&mut tournament);
+        tournament.advance_clock(Duration::from_secs(14 * 60));
+        tournament.start_hand().unwrap();
+        assert_eq!(tournament.level_number(), 1);
+
+        fold_around(&mut tournament);
+        tournament.advance_clock(Duration::from_secs(35 * 60));
+        tournament.start_hand().unwrap();
+        assert_eq!(tournament.level_number(), 3);
+        assert_eq!(tournament.game().blinds().big_blind, 200);
+    }
+
+    #[test]
+    fn test_eliminations_record_finishing_positions() {
+        let mut tournament = new_tournament(&[1000, 300, 200, 1000], SCHEDULE);
+
+        tournament.start_hand().unwrap();
+        fold_around(&mut tournament);
+        let table = tournament.game.table_mut().unwrap();
+        table.player_mut(0).unwrap().chips += 475;
+        table.player_mut(1).unwrap().chips = 0;
+        table.player_mut(2).unwrap().chips = 0;
+
+        let finishes = tournament.finish_hand().unwrap();
+        assert_eq!(
+            finishes,
+            vec![
+                Finish {
+                    name: "Ana",
+                    position: 3,
+                    hand: 1
+                },
+                Finish {
+                    name: "Marko",
+                    position: 4,
+                    hand: 1
+                },
+            ]
+        );
+        assert_eq!(tournament.game().table().occupied_seats().count(), 2);
+        assert!(!tournament.is_over());
+
+        tournament.start_hand().unwrap();
+        fold_around(&mut tournament);
+        let table = tournament.game.table_mut().unwrap();
+        let chips = table.player(3).unwrap().chips;
+        table.player_mut(0).unwrap().chips += chips;
+        table.player_mut(3).unwrap().chips = 0;
+
+        tournament.finish_hand().unwrap();
+        assert!(tournament.is_over());
+        assert_eq!(
+            tournament.start_hand(),
+            Err(TournamentError::TournamentOver)
+        );
+        assert_eq!(
+            tournament
+                .results()
+                .iter()
+                .map(|finish| (finish.position, finish.name))
+                .collect::<Vec<_>>(),
+            vec![(1, "Dusan"), (2, "Jelena"), (3, "Ana"), (4, "Marko")]
+        );
+    }
+
+    #[test]
+    fn test_equal_stacks_busting_together_share_a_place() {
+        let mut tournament = new_tournament(&[1000, 500, 500], SCHEDULE);
+
+        tournament.start_hand().unwrap();
+        fold_around(&mut tournament);
+        let table = tournament.game.table_mut().unwrap();
+        let chips = table.player(1).unwrap().chips + table.player(2).unwrap().chips;
+        table.player_mut(0).unwrap().chips += chips;
+        table.player_mut(1).unwrap().chips = 0;
+        table.player_mut(2).unwrap().chips = 0;
+
+        let finishes = tournament.finish_hand().unwrap();
+        assert_eq!(
+            finishes
+                .iter()
+                .map(|finish| (finish.position, finish.name))
+                .collect::<Vec<_>>(),
+            vec![(1, "Dusan"), (2, "Ana"), (2, "Marko")]
+        );
+    }
+}